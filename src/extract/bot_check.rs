@@ -0,0 +1,70 @@
+use reqwest::Url;
+use serde_json::Value;
+
+use super::helper;
+use super::error_msgs::BotCheckKind;
+use super::transport::HttpResponse;
+
+/// The help article the sign-in wall links to, whatever language the page is in.
+const BOT_CHECK_HELP_ARTICLE: &str = "support.google.com/youtube/answer/3037019";
+
+/// Recognises the pages YouTube and Google serve to traffic they take for a bot.
+///
+/// Only responses served as `text/html` and redirects to `/sorry/` are inspected, and caption
+/// tracks never are, so JSON and transcripts that happen to quote the same wording are never
+/// mistaken for a bot check.
+pub(crate) fn bot_check(response: &HttpResponse) -> Option<BotCheckKind> {
+    let path = Url::parse(&response.url).map(|url| url.path().to_string()).unwrap_or_default();
+    if path.starts_with("/api/timedtext") {
+        return None;
+    }
+    let on_sorry_page = path.starts_with("/sorry/");
+    let is_html = response.header("Content-Type").is_some_and(|value| value.contains("text/html"));
+    if !on_sorry_page && !is_html {
+        return None;
+    }
+
+    // A real watch page always has a player response; the sign-in wall is one of them.
+    if response.body.contains("ytInitialPlayerResponse") {
+        return is_sign_in_wall(&response.body).then_some(BotCheckKind::SignInWall);
+    }
+
+    let body = response.body.to_lowercase();
+    if body.contains("g-recaptcha") || body.contains("recaptcha/api") {
+        Some(BotCheckKind::Captcha)
+    } else if on_sorry_page || body.contains("unusual traffic") {
+        Some(BotCheckKind::UnusualTraffic)
+    } else if response.status == 429 && !body.trim().is_empty() {
+        Some(BotCheckKind::RateLimitPage)
+    } else {
+        None
+    }
+}
+
+/// `LOGIN_REQUIRED` is also used for private and age-restricted videos, so the error screen decides.
+///
+/// The reason text is localised, but the sign-in wall's "Learn more" link always points at
+/// the same help article; the English wording is kept as a fallback for pages without it.
+fn is_sign_in_wall(webpage: &str) -> bool {
+    if !webpage.contains("LOGIN_REQUIRED") {
+        return false;
+    }
+    let Some(player_response) = helper::extract_json_variable(webpage, "ytInitialPlayerResponse") else {
+        return false;
+    };
+    let status = player_response.get("playabilityStatus").unwrap_or(&Value::Null);
+    if helper::get_text_from_path(status, &["status"]).as_deref() != Some("LOGIN_REQUIRED") {
+        return false;
+    }
+
+    let links_bot_check_help = status
+        .pointer("/errorScreen/playerErrorMessageRenderer/subreason/runs")
+        .and_then(Value::as_array)
+        .is_some_and(|runs| runs.iter().any(|run| {
+            run.pointer("/navigationEndpoint/urlEndpoint/url")
+                .and_then(Value::as_str)
+                .is_some_and(|url| url.contains(BOT_CHECK_HELP_ARTICLE))
+        }));
+    let reason = helper::get_text_from_path(status, &["reason"]).unwrap_or_default().to_lowercase();
+    links_bot_check_help || reason.contains("not a bot")
+}
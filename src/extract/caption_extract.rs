@@ -0,0 +1,174 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use reqwest::Url;
+use serde_json::Value;
+use tracing::{debug, info};
+
+use crate::models::{CaptionFormat, CaptionKind, CaptionTrack, Transcript, TranscriptSegment};
+use super::YoutubeExtractor;
+use super::error_msgs::YoutubeError;
+use super::url::VideoId;
+
+static SRV3_PARAGRAPH_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)<p\s+t="(\d+)"(?:\s+d="(\d+)")?[^>]*>(.*?)</p>"#).expect("valid srv3 regex")
+});
+
+static XML_TAG_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<[^>]+>").expect("valid xml tag regex")
+});
+
+const CAPTION_TRACKS_PATH: [&str; 3] = ["captions", "playerCaptionsTracklistRenderer", "captionTracks"];
+
+impl YoutubeExtractor {
+
+    /// Lists the caption tracks offered for a video, both uploaded and auto-generated.
+    pub async fn list_caption_tracks(&self, video: &str) -> Result<Vec<CaptionTrack>, YoutubeError> {
+        let video_id: VideoId = video.parse()?;
+        let webpage = self.get_json(&video_id).await?;
+
+        let player_response = self.extract_player_response(&webpage);
+        self.check_playability(player_response.as_ref())?;
+        let player_response = player_response.ok_or_else(|| YoutubeError::SchemaChanged {
+            path: "ytInitialPlayerResponse".to_string(),
+        })?;
+
+        let tracks = self.get_caption_tracks(&player_response);
+        info!("🥎🥎 Found {} caption tracks for {} 🥎🥎", tracks.len(), video_id);
+        Ok(tracks)
+    }
+
+    /// Downloads one caption track and parses it into timed segments.
+    pub async fn fetch_transcript(&self, track: &CaptionTrack, format: CaptionFormat) -> Result<Transcript, YoutubeError> {
+        let mut url = Url::parse(&track.base_url)
+            .map_err(|_| YoutubeError::SchemaChanged { path: "captionTracks.baseUrl".to_string() })?;
+        let pairs: Vec<(String, String)> = url.query_pairs()
+            .filter(|(key, _)| key != "fmt")
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(pairs)
+            .append_pair("fmt", format.as_param());
+
+        let response = self.get_page(url.as_str()).await?;
+
+        let segments = match format {
+            CaptionFormat::Json3 => self.parse_json3_transcript(&response.body)?,
+            CaptionFormat::Srv3 => self.parse_srv3_transcript(&response.body),
+        };
+        debug!(language = %track.language_code, segments = segments.len(), "Fetched transcript");
+
+        Ok(Transcript {
+            video_id: track.video_id.clone(),
+            language_code: track.language_code.clone(),
+            kind: track.kind,
+            segments,
+        })
+    }
+
+    pub(crate) fn get_caption_tracks(&self, player_response: &Value) -> Vec<CaptionTrack> {
+        let video_id = self.get_text_from_path(player_response, &["videoDetails", "videoId"]).unwrap_or_default();
+
+        CAPTION_TRACKS_PATH.iter()
+            .try_fold(player_response, |value, key| value.get(key))
+            .and_then(|tracks| tracks.as_array())
+            .map(|tracks| {
+                tracks.iter()
+                    .filter_map(|track| Some(CaptionTrack {
+                        video_id: video_id.clone(),
+                        language_code: track.get("languageCode")?.as_str()?.to_string(),
+                        name: self.get_text_from_path(track, &["name", "simpleText"])
+                            .or_else(|| self.get_text_from_path(track, &["name"]))
+                            .unwrap_or_default(),
+                        kind: match track.get("kind").and_then(|k| k.as_str()) {
+                            Some("asr") => CaptionKind::Asr,
+                            _ => CaptionKind::Manual,
+                        },
+                        is_translatable: track.get("isTranslatable").and_then(|t| t.as_bool()).unwrap_or(false),
+                        base_url: track.get("baseUrl")?.as_str()?.to_string(),
+                    }))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// `fmt=json3`: events with `tStartMs`/`dDurationMs` and a list of text runs.
+    pub(crate) fn parse_json3_transcript(&self, body: &str) -> Result<Vec<TranscriptSegment>, YoutubeError> {
+        let data: Value = serde_json::from_str(body)?;
+        let events = data.get("events").and_then(|e| e.as_array()).cloned().unwrap_or_default();
+
+        Ok(events.iter()
+            .filter_map(|event| {
+                let text: String = event.get("segs")?
+                    .as_array()?
+                    .iter()
+                    .filter_map(|seg| seg.get("utf8").and_then(|t| t.as_str()))
+                    .collect();
+                let text = text.trim();
+                if text.is_empty() {
+                    return None;
+                }
+                Some(TranscriptSegment {
+                    start_seconds: event.get("tStartMs").and_then(|t| t.as_f64()).unwrap_or(0.0) / 1000.0,
+                    duration_seconds: event.get("dDurationMs").and_then(|d| d.as_f64()).unwrap_or(0.0) / 1000.0,
+                    text: text.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// `fmt=srv3`: `<p t="ms" d="ms">text</p>` elements, possibly with nested `<s>` runs.
+    pub(crate) fn parse_srv3_transcript(&self, body: &str) -> Vec<TranscriptSegment> {
+        SRV3_PARAGRAPH_PATTERN.captures_iter(body)
+            .filter_map(|caps| {
+                let start: f64 = caps.get(1)?.as_str().parse().ok()?;
+                let duration: f64 = caps.get(2).and_then(|d| d.as_str().parse().ok()).unwrap_or(0.0);
+                let text = decode_xml_entities(&XML_TAG_PATTERN.replace_all(&caps[3], ""));
+                let text = text.trim();
+                if text.is_empty() {
+                    return None;
+                }
+                Some(TranscriptSegment {
+                    start_seconds: start / 1000.0,
+                    duration_seconds: duration / 1000.0,
+                    text: text.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn decode_xml_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else { break };
+        let entity = &rest[1..end];
+        let ch = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match ch {
+            Some(ch) => {
+                decoded.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
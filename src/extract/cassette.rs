@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_std::fs;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+
+use super::error_msgs::YoutubeError;
+use super::helper::fnv1a_hash;
+use super::transport::{HttpResponse, Transport};
+
+/// One recorded request/response pair, stored as `<dir>/<key>.json`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CassetteEntry {
+    pub method: String,
+    pub url: String,
+    pub request_body: Option<Value>,
+    pub response: HttpResponse,
+}
+
+/// Response headers kept in cassettes. The rest, `Set-Cookie` above all, can carry session secrets.
+const RECORDED_HEADERS: &[&str] = &["Content-Type", "Retry-After", "Location"];
+
+/// Cassette key for a page fetch, derived from the method and URL.
+pub fn get_key(url: &str) -> String {
+    format!("get_{:016x}", fnv1a_hash(format!("GET {url}").as_bytes()))
+}
+
+/// Cassette key for an innertube call, derived from the method, endpoint URL and compact JSON request body.
+pub fn post_key(url: &str, body: &Value) -> String {
+    format!("post_{:016x}", fnv1a_hash(format!("POST {url}\n{body}").as_bytes()))
+}
+
+/// `response` with only the [`RECORDED_HEADERS`].
+fn redacted(response: &HttpResponse) -> HttpResponse {
+    let headers = response.headers.iter()
+        .filter(|(name, _)| RECORDED_HEADERS.iter().any(|kept| kept.eq_ignore_ascii_case(name)))
+        .cloned()
+        .collect();
+    HttpResponse { headers, ..response.clone() }
+}
+
+fn entry_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!("{key}.json"))
+}
+
+/// Wraps another [`Transport`] and writes every exchange into a cassette directory.
+///
+/// Responses are passed on untouched, but only their `Content-Type`, `Retry-After` and `Location`
+/// headers are written to disk.
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    dir: PathBuf,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Arc<dyn Transport>, dir: impl Into<PathBuf>) -> Self {
+        Self { inner, dir: dir.into() }
+    }
+
+    async fn record(&self, key: &str, entry: &CassetteEntry) -> Result<(), YoutubeError> {
+        fs::create_dir_all(&self.dir).await?;
+        let json_str = serde_json::to_string_pretty(entry)?;
+        fs::write(entry_path(&self.dir, key), json_str).await?;
+        debug!(key, url = %entry.url, "Recorded cassette entry");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        let response = self.inner.get_page(url, headers).await?;
+        let entry = CassetteEntry {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            request_body: None,
+            response: redacted(&response),
+        };
+        self.record(&get_key(url), &entry).await?;
+        Ok(response)
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        let response = self.inner.post_json(url, headers, body).await?;
+        let entry = CassetteEntry {
+            method: "POST".to_string(),
+            url: url.to_string(),
+            request_body: Some(body.clone()),
+            response: redacted(&response),
+        };
+        self.record(&post_key(url, body), &entry).await?;
+        Ok(response)
+    }
+}
+
+/// Serves responses from a cassette directory written by [`RecordingTransport`], without touching the network.
+pub struct ReplayTransport {
+    dir: PathBuf,
+}
+
+impl ReplayTransport {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    async fn replay(&self, key: String) -> Result<HttpResponse, YoutubeError> {
+        let path = entry_path(&self.dir, &key);
+        let json_str = match fs::read_to_string(&path).await {
+            Ok(json_str) => json_str,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(YoutubeError::CassetteMiss { key });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let entry: CassetteEntry = serde_json::from_str(&json_str)?;
+        debug!(key, url = %entry.url, "Replaying cassette entry");
+        Ok(entry.response)
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn get_page(&self, url: &str, _headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        self.replay(get_key(url)).await
+    }
+
+    async fn post_json(&self, url: &str, _headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        self.replay(post_key(url, body)).await
+    }
+}
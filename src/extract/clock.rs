@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+
+/// Source of the current time, used to stamp fetches and resolve "2 days ago" style dates.
+///
+/// Swap in a [`FixedClock`] through [`ExtractorBuilder::clock`](super::ExtractorBuilder::clock)
+/// to make extraction output reproducible.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock. Used unless the builder is given another one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock stopped at one instant.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
@@ -1,27 +1,34 @@
+use std::collections::HashSet;
+
 use async_std::fs;
+use chrono::{DateTime, Utc};
 use serde_json::{json, Value};
 use base64::{Engine as _, engine::general_purpose};
-use reqwest;
+use futures::{stream, StreamExt};
 use percent_encoding::percent_decode_str;
 use tracing::{warn, error, debug};
 
 use crate::models::Comment;
 use crate::models::CommentContent;
+use crate::models::MemberBadge;
 use helper::string_to_bool;
 
 
 use super::{helper, YoutubeExtractor};
+use super::locale::DisplayCount;
+use super::config::CommentSort;
+use super::comment_stream::PendingItem;
 use super::error_msgs::YoutubeError;
 
 impl YoutubeExtractor {
 
-    pub async fn comment_data_to_json(&self, data: &Vec<Comment>) -> Result<String, Box<dyn std::error::Error>> {
+    pub async fn comment_data_to_json(&self, data: &Vec<Comment>) -> Result<String, YoutubeError> {
         let string_json = serde_json::to_string_pretty(data)?;
         fs::write("final_comment_data.json", string_json).await?;
         Ok("Successfully wrote comments to COMMENT.json".to_string())
     }
 
-    pub async fn get_next_continuation_token(&self, data: &Value, request_count: &usize) -> Option<String> {
+    pub(crate) async fn get_next_continuation_token(&self, data: &Value, request_count: &usize) -> Option<String> {
         // Continuation tokens are in different path after initial request
         let not_initial_request = request_count > &1;
         let continuation_item_obj_alias = match not_initial_request {
@@ -46,63 +53,126 @@ impl YoutubeExtractor {
                 "continuationEndpoint",
                 "continuationCommand",
                 "token"
-            ]) {
-                if !token.is_empty() {
-                    return Some(token);
-                }
+            ]) && !token.is_empty() {
+                return Some(token);
             }
         }
         None
     }
 
-    pub async fn reply_extractor(&self, api_key: &String, continuation_token: &String, reply_count: &i32, comment_id: &String, video_id: &str, create_json_files: bool) -> Option<Vec<Comment> > {
-        let replies_json = self.comments_request(&api_key, &continuation_token, &0, false).await.unwrap_or_default();
+    /// Fetches every reply in a thread, following "Show more replies" continuations
+    /// until `reply_count` replies are collected or `max_reply_pages` is reached.
+    ///
+    /// A failed request fails the whole thread, so retrying it never repeats replies.
+    pub(crate) async fn reply_extractor(&self, api_key: &String, continuation_token: &str, reply_count: &u64, comment_id: &String, video_id: &str) -> Result<Vec<Comment>, YoutubeError> {
+        let replies_usize: usize = *reply_count as usize;
 
-        if create_json_files{
-            let replies_json_string = serde_json::to_string_pretty(&replies_json).unwrap_or_default();
-            let _ = fs::write("4_replies_4.json", replies_json_string).await;
-        }
+        // The count comes from the response, so it only sizes the first allocation up to a point.
+        let mut replies: Vec<Comment> = Vec::with_capacity(replies_usize.min(1_000));
+        let mut continuation_token = continuation_token.to_string();
+        let mut page_count = 0;
 
-        let replies_usize: usize = *reply_count as usize;
+        loop {
+            page_count += 1;
 
-        let mut replies: Vec<Comment> = Vec::with_capacity(replies_usize);
+            let (replies_json, fetched_at) = self.comments_request(api_key, &continuation_token, &0).await?;
 
-        let reply_content_list_actual = replies_json
-            .get("frameworkUpdates")?
-            .get( "entityBatchUpdate")?
-            .get("mutations")?
-            .as_array()?;
+            let replies_file_name = format!("4_{}_replies_4_{}.json", page_count, page_count);
+            self.dump_json(&replies_file_name, &replies_json).await;
 
-        let mut running_reply_count= 0;
+            let Some(reply_content_list_actual) = replies_json
+                .get("frameworkUpdates")
+                .and_then(|f| f.get("entityBatchUpdate"))
+                .and_then(|e| e.get("mutations"))
+                .and_then(|m| m.as_array()) else {
+                break;
+            };
 
-        for comment_content in reply_content_list_actual.iter() {
-            let reply_content = match self.get_comment_info(comment_content, video_id).await {
-                Some(content) => {
-                    running_reply_count += 1;
-                    content
-                },
-                None => {
+            let hearted_reply_ids = self.get_hearted_comment_ids(reply_content_list_actual);
+            for comment_content in reply_content_list_actual.iter() {
+                let Some(mut reply_content) = self.get_comment_info(comment_content, video_id, fetched_at).await else {
                     continue;
-                }
-            };
-            let reply = Comment::from_comment_content(reply_content, 1, comment_id.clone(), running_reply_count);
-            replies.push(reply);
+                };
+                reply_content.is_hearted = hearted_reply_ids.contains(&reply_content.comment_id);
+                // reply_order keeps counting across pages
+                let reply_order = replies.len() as i32 + 1;
+                replies.push(Comment::from_comment_content(reply_content, 1, comment_id.clone(), reply_order));
+            }
+
+            if replies.len() >= replies_usize {
+                break;
+            }
+            if page_count >= self.config().max_reply_pages {
+                debug!(comment_id = %comment_id, pages = page_count, "Reached max reply pages");
+                break;
+            }
+            match self.get_reply_continuation_token(&replies_json) {
+                Some(token) => continuation_token = token,
+                None => break,
+            }
         }
-        let extraction_diff = reply_count - replies.len() as i32;
+
+        let extraction_diff = reply_count.saturating_sub(replies.len() as u64);
         if extraction_diff > 0 {
             debug!(
                 comment_id = %comment_id,
                 expected_replies = reply_count,
                 extracted_replies = replies.len(),
                 missing_replies = extraction_diff,
+                pages = page_count,
                 "Reply extraction summary"
             );
         }
 
-        Some(replies)
+        Ok(replies)
+    }
+
+    /// The "Show more replies" token at the end of a reply page, if there are more replies.
+    pub(crate) fn get_reply_continuation_token(&self, data: &Value) -> Option<String> {
+        let continuation_items = data
+            .get("onResponseReceivedEndpoints")?
+            .as_array()?
+            .iter()
+            .filter_map(|endpoint| endpoint.get("appendContinuationItemsAction")?.get("continuationItems")?.as_array())
+            .flatten();
+
+        continuation_items
+            .filter_map(|item| {
+                let renderer = item.get("continuationItemRenderer")?;
+                self.get_text_from_path(renderer, &["button", "buttonRenderer", "command", "continuationCommand", "token"])
+                    .or_else(|| self.get_text_from_path(renderer, &["continuationEndpoint", "continuationCommand", "token"]))
+            })
+            .find(|token| !token.is_empty())
     }
 
-    pub async fn get_comment_info(&self, comment_content_json: &Value, video_id: &str) -> Option<CommentContent>{
+    /// IDs of comments the creator hearted. The heart lives in a separate
+    /// `engagementToolbarStateEntityPayload` mutation, linked through `toolbarStateKey`.
+    pub(crate) fn get_hearted_comment_ids(&self, mutations: &[Value]) -> HashSet<String> {
+        let hearted_keys: HashSet<String> = mutations.iter()
+            .filter_map(|mutation| mutation.get("payload")?.get("engagementToolbarStateEntityPayload"))
+            .filter(|state| state.get("heartState").and_then(|h| h.as_str()) == Some("TOOLBAR_HEART_STATE_HEARTED"))
+            .filter_map(|state| self.get_text_from_path(state, &["key"]))
+            .collect();
+
+        mutations.iter()
+            .filter_map(|mutation| mutation.get("payload")?.get("commentEntityPayload")?.get("properties"))
+            .filter(|properties| self.get_text_from_path(properties, &["toolbarStateKey"])
+                .is_some_and(|key| hearted_keys.contains(&key)))
+            .filter_map(|properties| self.get_text_from_path(properties, &["commentId"]))
+            .collect()
+    }
+
+    /// IDs of comment threads shown with a "Pinned by" label.
+    pub(crate) fn get_pinned_comment_ids(&self, continuation_items: &[Value]) -> HashSet<String> {
+        continuation_items.iter()
+            .filter_map(|item| item.get("commentThreadRenderer")?.get("commentViewModel")?.get("commentViewModel"))
+            .filter(|view_model| view_model.get("pinnedText").is_some())
+            .filter_map(|view_model| self.get_text_from_path(view_model, &["commentId"]))
+            .collect()
+    }
+
+    /// `fetched_at` is when the page holding the comment was received; relative dates are resolved against it.
+    pub(crate) async fn get_comment_info(&self, comment_content_json: &Value, video_id: &str, fetched_at: DateTime<Utc>) -> Option<CommentContent>{
         let author_info_json = match comment_content_json
             .get("payload")
             .and_then(|p| p.get("commentEntityPayload"))
@@ -128,45 +198,56 @@ impl YoutubeExtractor {
             .and_then(|c| c.get("toolbar"))
             .unwrap_or(&empty_toolbar_json);
 
-        let channel_id = self.get_text_from_path(&author_info_json, &["channelId"])
+        let channel_id = self.get_text_from_path(author_info_json, &["channelId"])
             .unwrap_or_else(|| "MISSING_CHANNEL_ID".to_string());
 
-        let display_name = self.get_text_from_path(&author_info_json, &["displayName"]).unwrap_or_else(|| "MISSING_DISPLAY_NAME".to_string());
+        let display_name = self.get_text_from_path(author_info_json, &["displayName"]).unwrap_or_else(|| "MISSING_DISPLAY_NAME".to_string());
 
-        let user_verified = match self.get_text_from_path(&author_info_json, &["isVerified"]) {
+        let user_verified = match self.get_text_from_path(author_info_json, &["isVerified"]) {
             Some(key) => string_to_bool(&key).unwrap_or(false),
             None => {
                 false
             }
         };
 
-        let thumbnail = self.get_text_from_path(&author_info_json, &["avatarThumbnailUrl"])
+        let thumbnail = self.get_text_from_path(author_info_json, &["avatarThumbnailUrl"])
             .unwrap_or_else(|| "MISSING_THUMBNAIL".to_string());
 
-        let comment_id = self.get_text_from_path(&comment_properties_json, &["commentId"])
+        let is_creator = self.get_text_from_path(author_info_json, &["isCreator"])
+            .and_then(|key| string_to_bool(&key))
+            .unwrap_or(false);
+
+        // canonicalBaseUrl is "/@handle" for channels that have one
+        let author_handle = self.get_text_from_path(author_info_json, &["channelCommand", "innertubeCommand", "browseEndpoint", "canonicalBaseUrl"])
+            .and_then(|url| url.strip_prefix('/').map(str::to_string))
+            .filter(|handle| handle.starts_with('@'))
+            .or_else(|| Some(display_name.clone()).filter(|name| name.starts_with('@')));
+
+        let member_badge = self.get_text_from_path(author_info_json, &["sponsorBadgeUrl"])
+            .map(|thumbnail_url| MemberBadge {
+                label: self.get_text_from_path(author_info_json, &["sponsorBadgeA11y"]).unwrap_or_default(),
+                thumbnail_url,
+            });
+
+        let comment_id = self.get_text_from_path(comment_properties_json, &["commentId"])
             .unwrap_or_else(|| "MISSING_COMMENT_ID".to_string());
 
-        let content = self.get_text_from_path(&comment_properties_json, &["content", "content"])
+        let content = self.get_text_from_path(comment_properties_json, &["content", "content"])
             .unwrap_or_else(|| "MISSING_CONTENT".to_string());
 
-        let published_time = self.get_text_from_path(&comment_properties_json, &["publishedTime"])
+        let published_time = self.get_text_from_path(comment_properties_json, &["publishedTime"])
             .unwrap_or_else(||"MISSING_PUBLISHED_TIME".to_string());
-
-        let like_count = match self.get_text_from_path(&toolbar_json, &["likeCountNotliked"]) {
-            Some(like) => like.parse().unwrap_or_default(),
-            None => 0
-        };
-
-        let reply_count = match self.get_text_from_path(&toolbar_json, &["replyCount"]) {
-            Some(reply) => {
-                if reply.is_empty(){
-                    0
-                } else {
-                    reply.parse().unwrap_or_default()
-                }
-            },
-            None => 0
+        let is_edited = self.is_edited(&published_time);
+        let published_at = self.parse_published_time(&published_time, fetched_at);
+
+        let count = |key: &str| match self.get_text_from_path(toolbar_json, &[key]) {
+            Some(text) if !text.trim().is_empty() => self.parse_display_count(&text)
+                .unwrap_or(DisplayCount { value: 0, exact: false }),
+            // Empty when nobody liked or replied yet
+            _ => DisplayCount { value: 0, exact: true },
         };
+        let like_count = count("likeCountNotliked");
+        let reply_count = count("replyCount");
 
         Some(CommentContent{
             comment_id,
@@ -177,28 +258,58 @@ impl YoutubeExtractor {
             thumbnail,
             content,
             published_time,
-            like_count,
-            reply_count,
+            published_at,
+            fetched_at,
+            like_count: like_count.value,
+            like_count_exact: like_count.exact,
+            reply_count: reply_count.value,
+            reply_count_exact: reply_count.exact,
+            author_handle,
+            is_creator,
+            is_pinned: false,
+            is_hearted: false,
+            is_edited,
+            member_badge,
         })
     }
-    pub async fn comment_extractor(&self, data: &Value, api_key: &String, video_id: &str, request_count: &usize, create_json_files: bool) -> Option<Vec<Comment>> {
-        let mut comments: Vec<Comment> = Vec::new();
-
-        if create_json_files{
-            let comment_content_list_test = data
-                .get("frameworkUpdates")?
-                .get( "entityBatchUpdate")?
-                .get("mutations")?;
-            let comment_test_string_pretty = serde_json::to_string_pretty(&comment_content_list_test).unwrap_or_default();
-            let main_comment_file_name = format!("2_{}_main_comment_content_2_{}.json", request_count, request_count);
-            let _ = fs::write(main_comment_file_name, comment_test_string_pretty).await;
-        }
+    pub(crate) async fn comment_extractor(&self, data: &Value, api_key: &String, video_id: &str, request_count: &usize) -> Result<Vec<Comment>, YoutubeError> {
+        let items = self.parse_comment_page(data, video_id, request_count, self.clock().now()).await?;
+
+        // Reply threads are fetched `reply_concurrency` at a time; `buffered` keeps page order.
+        let batches: Vec<Result<Vec<Comment>, YoutubeError>> = stream::iter(items)
+            .map(|item| async move {
+                match item {
+                    PendingItem::Comment(comment) => Ok(vec![*comment]),
+                    PendingItem::Replies { comment_id, continuation, reply_count } => {
+                        self.reply_extractor(api_key, &continuation, &reply_count, &comment_id, video_id).await
+                    }
+                }
+            })
+            .buffered(self.config().reply_concurrency.max(1))
+            .collect()
+            .await;
 
+        let batches: Vec<Vec<Comment>> = batches.into_iter().collect::<Result<_, _>>()?;
+        Ok(batches.into_iter().flatten().collect())
+    }
+
+    /// Splits a comments page into work items in output order: each thread's replies, then its top-level comment.
+    ///
+    /// A page without continuation items is not a comments page at all, so it fails rather than reading as empty.
+    pub(crate) async fn parse_comment_page(&self, data: &Value, video_id: &str, request_count: &usize, fetched_at: DateTime<Utc>) -> Result<Vec<PendingItem>, YoutubeError> {
+        let mut items: Vec<PendingItem> = Vec::new();
+
+        // Pages without comments, e.g. of a video nobody commented on yet, carry no mutations.
+        let empty_mutations = Vec::new();
         let comment_content_list_actual = data
-            .get("frameworkUpdates")?
-            .get( "entityBatchUpdate")?
-            .get("mutations")?
-            .as_array()?;
+            .get("frameworkUpdates")
+            .and_then(|f| f.get("entityBatchUpdate"))
+            .and_then(|e| e.get("mutations"))
+            .and_then(|m| m.as_array())
+            .unwrap_or(&empty_mutations);
+
+        let main_comment_file_name = format!("2_{}_main_comment_content_2_{}.json", request_count, request_count);
+        self.dump_json(&main_comment_file_name, comment_content_list_actual).await;
 
         debug!("comment_content_list_actual length == {}", comment_content_list_actual.len());
 
@@ -213,35 +324,40 @@ impl YoutubeExtractor {
         };
 
         let continuation_items_list_actual = data
-            .get("onResponseReceivedEndpoints")?
-            .get(continuation_items_index)?
-            .get(continuation_item_obj_alias)?
-            .get("continuationItems")?
-            .as_array()?;
+            .get("onResponseReceivedEndpoints")
+            .and_then(|endpoints| endpoints.get(continuation_items_index))
+            .and_then(|endpoint| endpoint.get(continuation_item_obj_alias))
+            .and_then(|action| action.get("continuationItems"))
+            .and_then(|items| items.as_array())
+            .ok_or_else(|| YoutubeError::SchemaChanged {
+                path: format!("onResponseReceivedEndpoints.{continuation_items_index}.{continuation_item_obj_alias}.continuationItems"),
+            })?;
 
-        if create_json_files{
-            let continuation_list_test = serde_json::to_string_pretty(&continuation_items_list_actual).unwrap_or_default();
-            let continuation_file_name = format!("3_{}_continuation_items_3_{}.json", request_count, request_count);
-            let _ = fs::write(continuation_file_name, continuation_list_test).await;
-        }
+        let continuation_file_name = format!("3_{}_continuation_items_3_{}.json", request_count, request_count);
+        self.dump_json(&continuation_file_name, continuation_items_list_actual).await;
+
+        let pinned_comment_ids = self.get_pinned_comment_ids(continuation_items_list_actual);
+        let hearted_comment_ids = self.get_hearted_comment_ids(comment_content_list_actual);
 
         for comment_content in comment_content_list_actual.iter() {
-            let comment_content = match self.get_comment_info(comment_content, video_id).await {
+            let mut comment_content = match self.get_comment_info(comment_content, video_id, fetched_at).await {
                 Some(content) => content,
                 None => {
                     continue;
                 }
             };
+            comment_content.is_pinned = pinned_comment_ids.contains(&comment_content.comment_id);
+            comment_content.is_hearted = hearted_comment_ids.contains(&comment_content.comment_id);
 
             // Getting individual reply continuation token
             if comment_content.reply_count > 0 {
                 let mut comment_continuation_token = "".to_string();
 
                 for continuation_block in continuation_items_list_actual.iter() {
-                    let continuation_comment_id = self.get_text_from_path(&continuation_block, &["commentThreadRenderer", "commentViewModel", "commentViewModel", "commentId"]).unwrap_or_default();
+                    let continuation_comment_id = self.get_text_from_path(continuation_block, &["commentThreadRenderer", "commentViewModel", "commentViewModel", "commentId"]).unwrap_or_default();
 
-                    if &continuation_comment_id == &comment_content.comment_id {
-                        comment_continuation_token = self.get_text_from_path(&continuation_block, &["commentThreadRenderer", "replies", "commentRepliesRenderer", "contents", "0", "continuationItemRenderer", "continuationEndpoint", "continuationCommand", "token"]).unwrap_or_default();
+                    if continuation_comment_id == comment_content.comment_id {
+                        comment_continuation_token = self.get_text_from_path(continuation_block, &["commentThreadRenderer", "replies", "commentRepliesRenderer", "contents", "0", "continuationItemRenderer", "continuationEndpoint", "continuationCommand", "token"]).unwrap_or_default();
                         if !comment_continuation_token.is_empty(){
                             break;
                         }
@@ -252,164 +368,172 @@ impl YoutubeExtractor {
                 if comment_continuation_token.is_empty() {
                     warn!("Failed to retrieve continuation token...")
                 } else {
-                    let mut replies = self.reply_extractor(&api_key, &comment_continuation_token, &comment_content.reply_count, &comment_content.comment_id, &video_id, create_json_files).await?;
-                    comments.append(&mut replies);
+                    items.push(PendingItem::Replies {
+                        comment_id: comment_content.comment_id.clone(),
+                        continuation: comment_continuation_token,
+                        reply_count: comment_content.reply_count,
+                    });
                 }
             }
 
-            let owned_video_id = video_id.to_owned();
-
-            let comment_info = Comment{
-                comment_id: comment_content.comment_id,
-                channel_id: comment_content.channel_id,
-                display_name: comment_content.display_name,
-                video_id: owned_video_id,
-                user_verified: comment_content.user_verified,
-                thumbnail: comment_content.thumbnail,
-                content: comment_content.content,
-                published_time: comment_content.published_time,
-                like_count: comment_content.like_count,
-                reply_count: comment_content.reply_count,
-                comment_level: 0,
-                reply_order: 0,
-                reply_to: "".to_string(),
-
-            };
-            comments.push(comment_info);
+            let comment_info = Comment::from_comment_content(comment_content, 0, "".to_string(), 0);
+            items.push(PendingItem::Comment(Box::new(comment_info)));
         }
 
-        Some(comments)
+        Ok(items)
     }
 
-    pub async fn comments_request(&self, api_key: &String, continuation: &String, request_count: &usize, create_json_files: bool) -> Result<Value, Box<dyn std::error::Error>> {
+    /// Sends one `youtubei/v1/next` request, returning the response with the instant it arrived.
+    ///
+    /// Every comment parsed from the response shares that instant as its `fetched_at`.
+    pub(crate) async fn comments_request(&self, api_key: &String, continuation: &String, request_count: &usize) -> Result<(Value, DateTime<Utc>), YoutubeError> {
         let url = format!("https://www.youtube.com/youtubei/v1/next?key={api_key}");
-        let client = reqwest::Client::new();
-        
+
         let decoded_continuation = if continuation.contains('%') {
-            let decoded = percent_decode_str(continuation)
-                .decode_utf8()
-                .unwrap()
-                .to_string();
-            decoded
+            percent_decode_str(continuation)
+                .decode_utf8_lossy()
+                .to_string()
         } else {
             continuation.to_string()
         };
 
         let payload = json!({
         "context": {
-            "client": {
-                "clientName": "WEB",
-                "clientVersion": "2.20240304.00.00"
-            }
+            "client": self.innertube_client()
         },
         "continuation": decoded_continuation
     });
         
-        let response = client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-            .header("Accept", "application/json")
-            .header("Accept-Language", "en-US,en;q=0.9")
-            .header("Origin", "https://www.youtube.com")
-            .header("Referer", "https://www.youtube.com/")
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| YoutubeError::ApiRequestError(Box::new(e)))?;
-
-        debug!("Comment Request Response Status: {}", response.status());
-
-        let response_text = response.text().await?;
-
-        let response_json: Value = serde_json::from_str(&response_text)?;
-
-        if request_count != &0 && create_json_files{
-            let response_string = serde_json::to_string_pretty(&response_json).unwrap_or_default();
+        let (url, payload) = (&url, &payload);
+        let response = self.send_with_retry(url, || async move {
+            let mut headers = self.request_headers(url);
+            headers.extend(self.authorization_headers());
+            headers.extend([
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Accept".to_string(), "application/json".to_string()),
+                ("Origin".to_string(), "https://www.youtube.com".to_string()),
+                ("Referer".to_string(), "https://www.youtube.com/".to_string()),
+            ]);
+
+            let (transport, proxy) = self.request_transport();
+            let response = transport.post_json(url, &headers, payload).await;
+            self.report_proxy(proxy, &response);
+            let response = response?;
+            self.store_cookies(url, &response);
+            debug!("Comment Request Response Status: {}", response.status);
+            Ok(response)
+        }).await?;
+        let fetched_at = self.clock().now();
+
+        let response_json: Value = serde_json::from_str(&response.body)?;
+
+        if request_count != &0 {
             let main_comment_file_name = format!("1_{}_main_comment_response_1_{}.json", request_count, request_count);
-            fs::write(main_comment_file_name, response_string).await.unwrap();
+            self.dump_json(&main_comment_file_name, &response_json).await;
         }
 
-        Ok(response_json)
+        Ok((response_json, fetched_at))
     }
 
-    pub fn get_api_key(&self, ytcfg: &Value) -> Result<String, YoutubeError> {
-        self.get_text_from_path(&ytcfg, &["INNERTUBE_API_KEY"]).ok_or(YoutubeError::ApiKeyNotFound)
+    pub(crate) fn get_api_key(&self, ytcfg: &Value) -> Result<String, YoutubeError> {
+        self.get_text_from_path(ytcfg, &["INNERTUBE_API_KEY"]).ok_or(YoutubeError::ApiKeyNotFound)
+    }
+    /// YouTube replaces the comment section with a message when comments are turned off.
+    pub(crate) fn comments_disabled(&self, data: &Value) -> bool {
+        let Some(sections) = data
+            .get("contents")
+            .and_then(|c| c.get("twoColumnWatchNextResults"))
+            .and_then(|c| c.get("results"))
+            .and_then(|c| c.get("results"))
+            .and_then(|c| c.get("contents"))
+            .and_then(|c| c.as_array()) else {
+            return false;
+        };
+
+        sections.iter().any(|section| {
+            self.get_text_from_path(section, &["itemSectionRenderer", "sectionIdentifier"]).as_deref() == Some("comment-item-section")
+                && section
+                    .get("itemSectionRenderer")
+                    .and_then(|r| r.get("contents"))
+                    .and_then(|c| c.get(0))
+                    .and_then(|c| c.get("messageRenderer"))
+                    .is_some()
+        })
     }
-    pub fn generate_synthetic_continuation_token(&self, video_id: &str) -> String {
+
+    pub(crate) fn generate_synthetic_continuation_token(&self, video_id: &str, sort: CommentSort) -> String {
         warn!("🥎🥎 Using a synthetic continuation token!! 🥎🥎");
-        let token = format!("\x12\r\x12\x0b{video_id}\x18\x062'\"\\x11\"\x0b{video_id}0\x00x\x020\x00B\x10comments-section");
+        // The varint right after the inner video ID is the sort menu index: 0 = top, 1 = newest,
+        // matching SORT_BY_POPULAR/SORT_BY_RECENT in youtube-comment-downloader.
+        let sort_byte = sort.menu_index() as u8 as char;
+        let token = format!("\x12\r\x12\x0b{video_id}\x18\x062'\"\x11\"\x0b{video_id}0{sort_byte}x\x020\x00B\x10comments-section");
         general_purpose::STANDARD.encode(token.as_bytes())
     }
-    pub fn get_continuation_token(&self, data: &Value, video_id: &str) -> String {
+
+    /// Finds the continuation for `sort` in the sort menu of the first comments response.
+    pub(crate) fn get_sort_continuation_token(&self, data: &Value, sort: CommentSort) -> Option<String> {
+        data.get("onResponseReceivedEndpoints")?
+            .as_array()?
+            .iter()
+            .filter_map(|endpoint| endpoint.get("reloadContinuationItemsCommand")?.get("continuationItems")?.as_array())
+            .flatten()
+            .find_map(|item| self.get_text_from_path(item, &[
+                "commentsHeaderRenderer", "sortMenu", "sortFilterSubMenuRenderer", "subMenuItems",
+                &sort.menu_index().to_string(), "serviceEndpoint", "continuationCommand", "token"
+            ]))
+            .filter(|token| !token.is_empty())
+    }
+
+    /// Swaps the default "Top comments" continuation for the one matching `sort`.
+    pub(crate) async fn sorted_continuation_token(&self, api_key: &String, continuation: &String, video_id: &str, sort: CommentSort) -> Result<String, YoutubeError> {
+        if sort == CommentSort::Top {
+            return Ok(continuation.clone());
+        }
+
+        let (top_comments, _) = self.comments_request(api_key, continuation, &0).await?;
+        match self.get_sort_continuation_token(&top_comments, sort) {
+            Some(token) => {
+                debug!(?sort, "Using sort menu continuation");
+                Ok(token)
+            }
+            None => Ok(self.generate_synthetic_continuation_token(video_id, sort)),
+        }
+    }
+
+    pub(crate) fn get_continuation_token(&self, data: &Value, video_id: &str) -> String {
         self.get_text_from_path(data, &[
             "engagementPanels", "0", "engagementPanelSectionListRenderer",
             "content", "sectionListRenderer", "contents", "0",
             "itemSectionRenderer", "contents", "0", "continuationItemRenderer",
             "continuationEndpoint", "continuationCommand", "token"
         ]).unwrap_or_else( || {
-            self.generate_synthetic_continuation_token(video_id)
+            self.generate_synthetic_continuation_token(video_id, CommentSort::Top)
         })
     }
 
-    pub async fn get_comments(&self, data: &Value, ytcfg: &Value, video_id: &str, max_requests: Option<usize>, create_json_files: bool) -> Result<Vec<Comment>, YoutubeError> {
-        let initial_continuation_token = self.get_continuation_token(&data, &video_id);
-        let api_key = self.get_api_key(&ytcfg)?;
+    /// Collects the comment stream for an already fetched watch page.
+    ///
+    /// Any error fails the whole extraction rather than passing off a truncated crawl as complete;
+    /// [`crawl_comments`](Self::crawl_comments) keeps what was gathered and can resume instead.
+    pub(crate) async fn get_comments(&self, data: &Value, ytcfg: &Value, video_id: &str) -> Result<Vec<Comment>, YoutubeError> {
+        let stream = self.comment_stream_from_page(data.clone(), ytcfg.clone(), video_id, self.config().comment_options());
+        let mut stream = std::pin::pin!(stream);
 
         let mut all_comments: Vec<Comment> = Vec::new();
-        let mut current_continuation = initial_continuation_token;
-        let mut request_count = 0;
-        let max_reqs = max_requests.unwrap_or(50);
-
-        loop {
-            request_count += 1;
-            debug!("Making request #{} for comments...", request_count);
-
-            let comments_data = match self.comments_request(&api_key, &current_continuation, &request_count, create_json_files).await {
-                Ok(data) => data,
+        while let Some(comment) = stream.next().await {
+            match comment {
+                Ok(comment) => all_comments.push(comment),
                 Err(e) => {
-                    error!("Error fetching comments on request {}: {:?}", request_count, e);
-                    break;
-                }
-            };
-
-            let batch_comments = self.comment_extractor(&comments_data, &api_key, video_id, &request_count, create_json_files)
-                .await
-                .unwrap_or_default();
-
-            debug!("Extracted {} comments from batch #{}", batch_comments.len(), request_count);
-
-            all_comments.extend(batch_comments);
-
-            let next_continuation_token = self.get_next_continuation_token(&comments_data, &request_count).await;
-
-            match next_continuation_token {
-                Some(token) if !token.is_empty() => {
-                    current_continuation = token;
-                    debug!("Found next continuation token, continuing...");
-
-                    if request_count >= max_reqs {
-                        debug!("Reached maximum request limit ({}), stopping.", max_reqs);
-                        break;
-                    }
-
-                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                }
-                _ => {
-                    debug!("No more continuation tokens found. Finished fetching comments.");
-                    break;
+                    error!("Error fetching comments after {} comments: {:?}", all_comments.len(), e);
+                    return Err(e);
                 }
             }
         }
 
         debug!("Total comments captured: {}", all_comments.len());
-        debug!("Made {} API requests", request_count);
 
-        if create_json_files {
-            let _ = self.comment_data_to_json(&all_comments).await;
-        }
+        self.dump_json("final_comment_data.json", &all_comments).await;
 
-        // format!("Successfully fetched {} comments in {} requests", all_comments.len(), request_count)
         Ok(all_comments)
     }
 }
\ No newline at end of file
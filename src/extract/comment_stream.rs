@@ -0,0 +1,317 @@
+use std::collections::VecDeque;
+
+use futures::{future, Stream, TryStreamExt};
+use futures::stream;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use crate::models::Comment;
+use super::YoutubeExtractor;
+use super::config::CommentOptions;
+use super::error_msgs::YoutubeError;
+use super::url::VideoId;
+
+/// Work left over from an already fetched comments page.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PendingItem {
+    /// A parsed comment that has not been yielded yet.
+    Comment(Box<Comment>),
+    /// A reply thread that has not been fetched yet.
+    Replies { comment_id: String, continuation: String, reply_count: u64 },
+}
+
+/// Everything needed to continue a comment crawl, emitted after each comments page is fetched.
+///
+/// All events yielded before a checkpoint are covered by it, so persisting the checkpoint after
+/// handling those events and passing it to [`YoutubeExtractor::resume_comments`] neither skips
+/// nor repeats comments.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CrawlCheckpoint {
+    pub video_id: String,
+    pub api_key: String,
+    pub options: CommentOptions,
+    /// Continuation of the next top-level page, `None` once the last page has been fetched.
+    pub continuation: Option<String>,
+    /// Top-level pages requested so far.
+    pub request_count: usize,
+    /// Comments and reply threads of the current page that come next, in order.
+    pub pending: Vec<PendingItem>,
+    /// Comments yielded so far, including replies.
+    pub emitted: usize,
+}
+
+/// An item of [`YoutubeExtractor::crawl_comments`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CrawlEvent {
+    Comment(Comment),
+    Checkpoint(CrawlCheckpoint),
+}
+
+/// Where a crawl gets its first continuation from.
+enum CrawlStart {
+    /// Fetch the watch page first.
+    Video(String),
+    /// The watch page was already fetched by [`YoutubeExtractor::extract`].
+    Page { initial_data: Value, ytcfg: Value, video_id: String },
+}
+
+/// State carried between items of a comment stream.
+struct CommentCrawl {
+    extractor: YoutubeExtractor,
+    options: CommentOptions,
+    start: Option<CrawlStart>,
+    video_id: String,
+    api_key: String,
+    continuation: Option<String>,
+    request_count: usize,
+    emitted: usize,
+    pending: VecDeque<PendingItem>,
+    checkpoint_due: bool,
+    finished: bool,
+    /// Yielded right after the checkpoint that lets a resumed crawl retry what failed.
+    error: Option<YoutubeError>,
+    /// Set once an error has been yielded, which ends the stream.
+    failed: bool,
+}
+
+impl YoutubeExtractor {
+
+    /// Streams a video's comments, with each thread's replies ahead of the comment they answer.
+    ///
+    /// A `youtubei/v1/next` page is only requested once the previous one has been consumed,
+    /// so dropping the stream stops the crawl. An error ends the stream.
+    pub fn comments_stream(&self, video: &str, options: CommentOptions) -> impl Stream<Item = Result<Comment, YoutubeError>> + Send + 'static {
+        only_comments(self.crawl_comments(video, options))
+    }
+
+    /// Like [`comments_stream`](Self::comments_stream), but also yields a [`CrawlCheckpoint`] after every
+    /// comments page and right before the error of a failed reply thread.
+    pub fn crawl_comments(&self, video: &str, options: CommentOptions) -> impl Stream<Item = Result<CrawlEvent, YoutubeError>> + Send + 'static {
+        self.crawl_stream(self.new_crawl(Some(CrawlStart::Video(video.to_string())), options))
+    }
+
+    /// Continues a crawl from a checkpoint, with the options it was started with.
+    pub fn resume_comments(&self, checkpoint: CrawlCheckpoint) -> impl Stream<Item = Result<CrawlEvent, YoutubeError>> + Send + 'static {
+        info!(video_id = %checkpoint.video_id, request_count = checkpoint.request_count, emitted = checkpoint.emitted, "Resuming comment crawl");
+        let crawl = CommentCrawl {
+            video_id: checkpoint.video_id,
+            api_key: checkpoint.api_key,
+            continuation: checkpoint.continuation,
+            request_count: checkpoint.request_count,
+            emitted: checkpoint.emitted,
+            pending: checkpoint.pending.into(),
+            ..self.new_crawl(None, checkpoint.options)
+        };
+        self.crawl_stream(crawl)
+    }
+
+    pub(crate) fn comment_stream_from_page(&self, initial_data: Value, ytcfg: Value, video_id: &str, options: CommentOptions) -> impl Stream<Item = Result<Comment, YoutubeError>> + Send + 'static {
+        let video_id = video_id.to_string();
+        let start = CrawlStart::Page { initial_data, ytcfg, video_id };
+        only_comments(self.crawl_stream(self.new_crawl(Some(start), options)))
+    }
+
+    fn new_crawl(&self, start: Option<CrawlStart>, options: CommentOptions) -> CommentCrawl {
+        CommentCrawl {
+            extractor: self.for_video(),
+            options,
+            start,
+            video_id: String::new(),
+            api_key: String::new(),
+            continuation: None,
+            request_count: 0,
+            emitted: 0,
+            pending: VecDeque::new(),
+            checkpoint_due: false,
+            finished: false,
+            error: None,
+            failed: false,
+        }
+    }
+
+    fn crawl_stream(&self, crawl: CommentCrawl) -> impl Stream<Item = Result<CrawlEvent, YoutubeError>> + Send + 'static {
+        stream::unfold(crawl, |mut crawl| async move {
+            let event = crawl.next_event().await?;
+            Some((event, crawl))
+        })
+    }
+}
+
+fn only_comments(events: impl Stream<Item = Result<CrawlEvent, YoutubeError>>) -> impl Stream<Item = Result<Comment, YoutubeError>> {
+    events.try_filter_map(|event| async move {
+        Ok(match event {
+            CrawlEvent::Comment(comment) => Some(comment),
+            CrawlEvent::Checkpoint(_) => None,
+        })
+    })
+}
+
+impl CommentCrawl {
+    async fn next_event(&mut self) -> Option<Result<CrawlEvent, YoutubeError>> {
+        loop {
+            if let Some(e) = self.error.take() {
+                self.failed = true;
+                return Some(Err(e));
+            }
+            if self.failed {
+                return None;
+            }
+            if let Some(max_comments) = self.options.max_comments
+                && self.emitted >= max_comments {
+                debug!("Reached maximum comment limit ({}), stopping.", max_comments);
+                return None;
+            }
+            if self.checkpoint_due {
+                self.checkpoint_due = false;
+                return Some(Ok(CrawlEvent::Checkpoint(self.checkpoint())));
+            }
+
+            match self.pending.pop_front() {
+                Some(PendingItem::Comment(comment)) => {
+                    self.emitted += 1;
+                    return Some(Ok(CrawlEvent::Comment(*comment)));
+                }
+                Some(thread @ PendingItem::Replies { .. }) => {
+                    self.pending.push_front(thread);
+                    if let Err(e) = self.fetch_replies().await {
+                        self.error = Some(e);
+                        return Some(Ok(CrawlEvent::Checkpoint(self.checkpoint())));
+                    }
+                }
+                None if self.finished => return None,
+                None => {
+                    if let Err(e) = self.fetch_next_page().await {
+                        self.failed = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+    }
+
+    fn checkpoint(&self) -> CrawlCheckpoint {
+        CrawlCheckpoint {
+            video_id: self.video_id.clone(),
+            api_key: self.api_key.clone(),
+            options: self.options.clone(),
+            continuation: self.continuation.clone(),
+            request_count: self.request_count,
+            pending: self.pending.iter().cloned().collect(),
+            emitted: self.emitted,
+        }
+    }
+
+    async fn begin(&mut self, start: CrawlStart) -> Result<(), YoutubeError> {
+        let extractor = &self.extractor;
+        let (initial_data, ytcfg, video_id) = match start {
+            CrawlStart::Page { initial_data, ytcfg, video_id } => (initial_data, ytcfg, video_id),
+            CrawlStart::Video(video) => {
+                let video_id: VideoId = video.parse()?;
+                let webpage = extractor.get_json(&video_id).await?;
+                extractor.check_playability(extractor.extract_player_response(&webpage).as_ref())?;
+                let initial_data = extractor.extract_initial_data(&webpage)?;
+                let ytcfg = extractor.extract_ytcfg(&webpage).await?;
+                (initial_data, ytcfg, video_id.to_string())
+            }
+        };
+
+        if extractor.comments_disabled(&initial_data) {
+            return Err(YoutubeError::CommentsDisabled);
+        }
+
+        let api_key = extractor.get_api_key(&ytcfg)?;
+        let continuation = extractor.get_continuation_token(&initial_data, &video_id);
+        let continuation = extractor.sorted_continuation_token(&api_key, &continuation, &video_id, self.options.sort).await?;
+
+        info!(video_id = %video_id, sort = ?self.options.sort, "Starting comment crawl");
+        self.video_id = video_id;
+        self.api_key = api_key;
+        self.continuation = Some(continuation);
+        Ok(())
+    }
+
+    async fn fetch_next_page(&mut self) -> Result<(), YoutubeError> {
+        if let Some(start) = self.start.take() {
+            self.begin(start).await?;
+        }
+
+        let Some(continuation) = self.continuation.take() else {
+            debug!("No more continuation tokens found. Finished fetching comments.");
+            self.finished = true;
+            return Ok(());
+        };
+        if self.request_count >= self.options.max_pages {
+            debug!("Reached maximum request limit ({}), stopping.", self.options.max_pages);
+            self.finished = true;
+            return Ok(());
+        }
+        let request_count = self.request_count + 1;
+        debug!("Making request #{} for comments...", request_count);
+
+        let extractor = &self.extractor;
+        let page = async {
+            let (comments_data, fetched_at) = extractor.comments_request(&self.api_key, &continuation, &request_count).await?;
+            let items = extractor.parse_comment_page(&comments_data, &self.video_id, &request_count, fetched_at).await?;
+            Ok::<_, YoutubeError>((comments_data, items))
+        };
+        let (comments_data, items) = match page.await {
+            Ok(page) => page,
+            Err(e) => {
+                // Put the token back so the crawl still matches the last checkpoint, which retries this page.
+                self.continuation = Some(continuation);
+                return Err(e);
+            }
+        };
+        debug!("Parsed {} items from batch #{}", items.len(), request_count);
+
+        self.request_count = request_count;
+        self.pending.extend(items);
+        self.continuation = extractor.get_next_continuation_token(&comments_data, &request_count)
+            .await
+            .filter(|token| !token.is_empty());
+        self.checkpoint_due = true;
+        Ok(())
+    }
+
+    /// Fetches the next `reply_concurrency` pending reply threads at once and puts their replies in their place.
+    ///
+    /// A thread that fails stays pending while the others are replaced by their replies, so the
+    /// checkpoint yielded before the error retries only the failed threads. The first failure is returned.
+    async fn fetch_replies(&mut self) -> Result<(), YoutubeError> {
+        let concurrency = self.extractor.config().reply_concurrency.max(1);
+        let threads: Vec<(usize, String, String, u64)> = self.pending.iter()
+            .enumerate()
+            .filter_map(|(index, item)| match item {
+                PendingItem::Replies { comment_id, continuation, reply_count } => {
+                    Some((index, comment_id.clone(), continuation.clone(), *reply_count))
+                }
+                PendingItem::Comment(_) => None,
+            })
+            .take(concurrency)
+            .collect();
+
+        let (extractor, api_key, video_id) = (&self.extractor, &self.api_key, &self.video_id);
+        let results = future::join_all(threads.iter().map(|(_, comment_id, continuation, reply_count)| {
+            extractor.reply_extractor(api_key, continuation, reply_count, comment_id, video_id)
+        })).await;
+
+        // Splice back to front so earlier indices stay valid.
+        let mut failure = None;
+        for ((index, comment_id, ..), replies) in threads.iter().zip(results).rev() {
+            let replies = match replies {
+                Ok(replies) => replies,
+                Err(e) => {
+                    warn!(error = %e, comment_id = %comment_id, "Failed to fetch replies");
+                    failure = Some(e);
+                    continue;
+                }
+            };
+            self.pending.remove(*index);
+            for (offset, reply) in replies.into_iter().enumerate() {
+                self.pending.insert(index + offset, PendingItem::Comment(Box::new(reply)));
+            }
+        }
+        failure.map_or(Ok(()), Err)
+    }
+}
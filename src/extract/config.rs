@@ -0,0 +1,366 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::YoutubeExtractor;
+use super::cassette::{RecordingTransport, ReplayTransport};
+use super::clock::Clock;
+use super::cookies::CookieJar;
+use super::error_msgs::YoutubeError;
+use super::proxy::ProxyPool;
+use super::rate_limit::RateLimiter;
+use super::retry::RetryPolicy;
+use super::transport::{ReqwestTransport, Transport};
+
+/// Order in which top-level comments are fetched, matching the comment section's sort menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CommentSort {
+    /// YouTube's default "Top comments" ranking.
+    #[default]
+    Top,
+    /// "Newest first".
+    Newest,
+}
+
+impl CommentSort {
+    /// Position of this option in `sortFilterSubMenuRenderer.subMenuItems`.
+    pub(crate) fn menu_index(&self) -> usize {
+        match self {
+            CommentSort::Top => 0,
+            CommentSort::Newest => 1,
+        }
+    }
+}
+
+/// Settings for a single comment crawl.
+///
+/// `CommentOptions::default()` matches `ExtractorConfig::default()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentOptions {
+    pub sort: CommentSort,
+    /// Maximum number of top-level comment pages requested.
+    pub max_pages: usize,
+    /// Stop once this many comments (including replies) have been yielded.
+    pub max_comments: Option<usize>,
+}
+
+impl Default for CommentOptions {
+    fn default() -> Self {
+        ExtractorConfig::default().comment_options()
+    }
+}
+
+impl CommentOptions {
+    pub fn sort(mut self, sort: CommentSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn max_pages(mut self, pages: usize) -> Self {
+        self.max_pages = pages;
+        self
+    }
+
+    pub fn max_comments(mut self, comments: usize) -> Self {
+        self.max_comments = Some(comments);
+        self
+    }
+}
+
+/// Tunable settings for a [`YoutubeExtractor`].
+///
+/// Build one through [`YoutubeExtractor::builder`] or start from `ExtractorConfig::default()`.
+#[derive(Debug, Clone)]
+pub struct ExtractorConfig {
+    /// Maximum number of top-level comment pages requested per video.
+    pub max_comment_pages: usize,
+    /// Stop collecting once this many comments (including replies) have been gathered.
+    pub max_comments: Option<usize>,
+    /// Maximum number of reply pages requested per comment thread.
+    pub max_reply_pages: usize,
+    pub comment_sort: CommentSort,
+    /// Minimum spacing between requests, shared by concurrent reply fetches.
+    pub request_delay: Duration,
+    /// Requests allowed back to back after a quiet period before `request_delay` spacing kicks in.
+    pub request_burst: u32,
+    /// How throttled, failed and dropped requests are retried.
+    pub retry: RetryPolicy,
+    /// How many reply threads of a comments page are fetched at once.
+    pub reply_concurrency: usize,
+    /// Innertube `clientName` sent with every comment request.
+    pub client_name: String,
+    /// Innertube `clientVersion` sent with every comment request.
+    pub client_version: String,
+    /// Interface language (`hl`), e.g. `en` or `pt-BR`. Sent on every request and used to parse counts and dates.
+    pub language: String,
+    /// Content region (`gl`), e.g. `US`. Sent on every request.
+    pub region: String,
+    /// Pre-sets the `SOCS`/`CONSENT` cookies a browser gets after answering YouTube's EU cookie banner,
+    /// so EU egress gets the video instead of the consent interstitial.
+    pub bypass_consent: bool,
+    pub user_agent: String,
+    /// Total time allowed for a single HTTP request.
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    /// When set, raw responses and extracted data are written here as JSON for debugging.
+    pub debug_dump_dir: Option<PathBuf>,
+}
+
+impl Default for ExtractorConfig {
+    fn default() -> Self {
+        Self {
+            max_comment_pages: 25,
+            max_comments: None,
+            max_reply_pages: 50,
+            comment_sort: CommentSort::Top,
+            request_delay: Duration::from_millis(100),
+            request_burst: 1,
+            retry: RetryPolicy::default(),
+            reply_concurrency: 4,
+            client_name: "WEB".to_string(),
+            client_version: "2.20240304.00.00".to_string(),
+            language: "en".to_string(),
+            region: "US".to_string(),
+            bypass_consent: true,
+            user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36".to_string(),
+            timeout: Some(Duration::from_secs(30)),
+            connect_timeout: Some(Duration::from_secs(10)),
+            debug_dump_dir: None,
+        }
+    }
+}
+
+impl ExtractorConfig {
+    /// The comment settings of this config, as a starting point for [`YoutubeExtractor::comments_stream`].
+    pub fn comment_options(&self) -> CommentOptions {
+        CommentOptions {
+            sort: self.comment_sort,
+            max_pages: self.max_comment_pages,
+            max_comments: self.max_comments,
+        }
+    }
+
+    pub(crate) fn accept_language(&self) -> String {
+        let primary = self.language.split('-').next().unwrap_or_default();
+        if self.language.contains('-') {
+            format!("{},{};q=0.9", self.language, primary)
+        } else {
+            format!("{}-{},{};q=0.9", self.language, self.region, self.language)
+        }
+    }
+
+    /// Cookies sent with every request. `PREF` keeps `hl`/`gl` even where the URL parameters are dropped.
+    pub(crate) fn cookies(&self) -> Vec<(String, String)> {
+        let mut cookies = vec![("PREF".to_string(), format!("hl={}&gl={}", self.language, self.region))];
+        if self.bypass_consent {
+            // SOCS=CAI is the current "accepted" answer, CONSENT=YES+ the one older frontends still check.
+            cookies.push(("SOCS".to_string(), "CAI".to_string()));
+            cookies.push(("CONSENT".to_string(), "YES+cb".to_string()));
+        }
+        cookies
+    }
+}
+
+/// Builder returned by [`YoutubeExtractor::builder`].
+#[derive(Clone, Default)]
+pub struct ExtractorBuilder {
+    config: ExtractorConfig,
+    transport: Option<Arc<dyn Transport>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    clock: Option<Arc<dyn Clock>>,
+    cookie_jar: Option<Arc<CookieJar>>,
+    proxy_pool: Option<Arc<ProxyPool>>,
+    record_dir: Option<PathBuf>,
+}
+
+impl ExtractorBuilder {
+    pub fn max_comment_pages(mut self, pages: usize) -> Self {
+        self.config.max_comment_pages = pages;
+        self
+    }
+
+    pub fn max_comments(mut self, comments: usize) -> Self {
+        self.config.max_comments = Some(comments);
+        self
+    }
+
+    pub fn max_reply_pages(mut self, pages: usize) -> Self {
+        self.config.max_reply_pages = pages;
+        self
+    }
+
+    pub fn comment_sort(mut self, sort: CommentSort) -> Self {
+        self.config.comment_sort = sort;
+        self
+    }
+
+    pub fn request_delay(mut self, delay: Duration) -> Self {
+        self.config.request_delay = delay;
+        self
+    }
+
+    /// Values below 1 are treated as 1.
+    pub fn request_burst(mut self, burst: u32) -> Self {
+        self.config.request_burst = burst;
+        self
+    }
+
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.config.retry = policy;
+        self
+    }
+
+    /// Values below 1 are treated as 1.
+    pub fn reply_concurrency(mut self, threads: usize) -> Self {
+        self.config.reply_concurrency = threads;
+        self
+    }
+
+    pub fn client_name(mut self, name: impl Into<String>) -> Self {
+        self.config.client_name = name.into();
+        self
+    }
+
+    pub fn client_version(mut self, version: impl Into<String>) -> Self {
+        self.config.client_version = version.into();
+        self
+    }
+
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.config.language = language.into();
+        self
+    }
+
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.config.region = region.into();
+        self
+    }
+
+    pub fn bypass_consent(mut self, bypass: bool) -> Self {
+        self.config.bypass_consent = bypass;
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn debug_dump_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.debug_dump_dir = Some(dir.into());
+        self
+    }
+
+    /// Replaces the default pooled reqwest transport.
+    ///
+    /// Timeouts from this builder only apply to the default transport.
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Shares a limiter between extractors instead of giving this one its own, overriding `request_delay` and `request_burst`.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Replaces the system clock used to stamp fetches and resolve relative dates.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Starts from these cookies, e.g. a logged-in browser session, instead of an empty jar.
+    ///
+    /// Cookies YouTube sets while extracting are written back into the same jar.
+    pub fn cookie_jar(mut self, cookie_jar: Arc<CookieJar>) -> Self {
+        self.cookie_jar = Some(cookie_jar);
+        self
+    }
+
+    /// Sends watch page, innertube and caption requests through these proxies instead of the transport.
+    ///
+    /// Cannot be combined with `transport`, `replay_from` or `record_to`, whose transport the pool would bypass.
+    pub fn proxy_pool(mut self, proxy_pool: Arc<ProxyPool>) -> Self {
+        self.proxy_pool = Some(proxy_pool);
+        self
+    }
+
+    /// Records every request/response pair into a cassette directory.
+    pub fn record_to(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.record_dir = Some(dir.into());
+        self
+    }
+
+    /// Serves all requests from a recorded cassette directory instead of the network.
+    pub fn replay_from(self, dir: impl Into<PathBuf>) -> Self {
+        self.transport(Arc::new(ReplayTransport::new(dir)))
+    }
+
+    /// Like [`try_build`](Self::try_build), for configurations known to be valid.
+    ///
+    /// # Panics
+    ///
+    /// If a proxy pool is combined with a custom transport, `replay_from` or `record_to`, or if
+    /// the HTTP client cannot be built.
+    pub fn build(self) -> YoutubeExtractor {
+        self.try_build().expect("valid extractor configuration")
+    }
+
+    /// Builds the extractor, rejecting settings that contradict each other and failing if the HTTP client cannot be built.
+    pub fn try_build(self) -> Result<YoutubeExtractor, YoutubeError> {
+        if self.proxy_pool.is_some() {
+            let conflict = if self.record_dir.is_some() {
+                Some("record_to")
+            } else if self.transport.is_some() {
+                Some("a custom transport or replay_from")
+            } else {
+                None
+            };
+            if let Some(conflict) = conflict {
+                return Err(YoutubeError::InvalidConfig {
+                    reason: format!("a proxy pool sends requests past {conflict}, so the two cannot be combined"),
+                });
+            }
+        }
+
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => Arc::new(ReqwestTransport::new(&self.config)?),
+        };
+        let transport = match self.record_dir {
+            Some(dir) => Arc::new(RecordingTransport::new(transport, dir)),
+            None => transport,
+        };
+        let extractor = YoutubeExtractor::with_transport(self.config, transport);
+        let extractor = match self.rate_limiter {
+            Some(rate_limiter) => extractor.with_rate_limiter(rate_limiter),
+            None => extractor,
+        };
+        let extractor = match self.clock {
+            Some(clock) => extractor.with_clock(clock),
+            None => extractor,
+        };
+        let extractor = match self.cookie_jar {
+            Some(cookie_jar) => extractor.with_cookie_jar(cookie_jar),
+            None => extractor,
+        };
+        Ok(match self.proxy_pool {
+            Some(proxy_pool) => extractor.with_proxy_pool(proxy_pool),
+            None => extractor,
+        })
+    }
+}
@@ -0,0 +1,257 @@
+use std::path::Path;
+use std::sync::RwLock;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::Url;
+
+use super::error_msgs::YoutubeError;
+
+/// One cookie, as stored in a Netscape `cookies.txt` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    /// Host the cookie belongs to, without a leading dot.
+    pub domain: String,
+    /// Also sent to subdomains of `domain`.
+    pub include_subdomains: bool,
+    pub path: String,
+    /// Only sent over https.
+    pub secure: bool,
+    /// `None` for session cookies.
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl Cookie {
+    /// A session cookie for `domain` and its subdomains.
+    pub fn new(domain: impl Into<String>, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let domain: String = domain.into();
+        Self {
+            name: name.into(),
+            value: value.into(),
+            domain: domain.trim_start_matches('.').to_string(),
+            include_subdomains: true,
+            path: "/".to_string(),
+            secure: true,
+            expires: None,
+        }
+    }
+
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let domain_matches = host == self.domain
+            || (self.include_subdomains && host.strip_suffix(self.domain.as_str()).is_some_and(|rest| rest.ends_with('.')));
+        domain_matches
+            && url.path().starts_with(&self.path)
+            && (!self.secure || url.scheme() == "https")
+    }
+
+    fn same_slot(&self, other: &Cookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+
+    /// Parses one `Set-Cookie` header value sent in response to `url`.
+    ///
+    /// A cookie whose `Domain` the host of `url` does not belong to is ignored, so a response
+    /// cannot plant cookies for other sites or for a whole top-level domain.
+    fn from_set_cookie(header: &str, url: &Url, now: DateTime<Utc>) -> Option<Self> {
+        let mut attributes = header.split(';');
+        let (name, value) = attributes.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let host = url.host_str()?.to_ascii_lowercase();
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            include_subdomains: false,
+            path: "/".to_string(),
+            secure: false,
+            expires: None,
+        };
+        let mut max_age = None;
+
+        for attribute in attributes {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    if domain == host {
+                        cookie.include_subdomains = true;
+                    } else if domain.contains('.') && host.strip_suffix(domain.as_str()).is_some_and(|rest| rest.ends_with('.')) {
+                        cookie.domain = domain;
+                        cookie.include_subdomains = true;
+                    } else {
+                        return None;
+                    }
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "max-age" => max_age = value.parse::<i64>().ok(),
+                "expires" => {
+                    cookie.expires = DateTime::parse_from_rfc2822(&value.replace('-', " "))
+                        .ok()
+                        .map(|expires| expires.with_timezone(&Utc));
+                }
+                _ => {}
+            }
+        }
+
+        // Max-Age wins over Expires when both are present; one beyond the date range never expires.
+        if let Some(seconds) = max_age {
+            cookie.expires = TimeDelta::try_seconds(seconds).and_then(|max_age| now.checked_add_signed(max_age));
+        }
+        Some(cookie)
+    }
+
+    fn from_netscape_line(line: &str) -> Option<Self> {
+        let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+        if line.trim().is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('\t').collect();
+        let [domain, include_subdomains, path, secure, expires, name, value] = fields[..] else {
+            return None;
+        };
+        let expires = expires.trim().parse::<i64>().ok()?;
+
+        Some(Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: domain.trim_start_matches('.').to_string(),
+            include_subdomains: include_subdomains.eq_ignore_ascii_case("TRUE"),
+            path: path.to_string(),
+            secure: secure.eq_ignore_ascii_case("TRUE"),
+            expires: (expires > 0).then(|| DateTime::from_timestamp(expires, 0)).flatten(),
+        })
+    }
+
+    fn to_netscape_line(&self) -> String {
+        let flag = |value: bool| if value { "TRUE" } else { "FALSE" };
+        let domain = if self.include_subdomains {
+            format!(".{}", self.domain)
+        } else {
+            self.domain.clone()
+        };
+        format!(
+            "{domain}\t{}\t{}\t{}\t{}\t{}\t{}",
+            flag(self.include_subdomains),
+            self.path,
+            flag(self.secure),
+            self.expires.map_or(0, |expires| expires.timestamp()),
+            self.name,
+            self.value,
+        )
+    }
+}
+
+/// Cookies sent with every request and updated from every `Set-Cookie` YouTube answers with.
+///
+/// Every clone of a [`YoutubeExtractor`](super::YoutubeExtractor) shares one jar. Load a browser
+/// export with [`from_netscape_file`](Self::from_netscape_file) and pass it to
+/// [`ExtractorBuilder::cookie_jar`](super::ExtractorBuilder::cookie_jar) to make logged-in requests.
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    cookies: RwLock<Vec<Cookie>>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a Netscape `cookies.txt` file, as exported by browser extensions or `yt-dlp --cookies`.
+    pub fn from_netscape_file(path: impl AsRef<Path>) -> Result<Self, YoutubeError> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(Self::parse_netscape(&text))
+    }
+
+    /// Parses the contents of a Netscape `cookies.txt` file. Malformed lines are skipped.
+    pub fn parse_netscape(text: &str) -> Self {
+        let jar = Self::new();
+        text.lines()
+            .filter_map(Cookie::from_netscape_line)
+            .for_each(|cookie| jar.insert(cookie));
+        jar
+    }
+
+    /// The jar in Netscape `cookies.txt` format, session cookies included.
+    pub fn to_netscape(&self) -> String {
+        let mut text = String::from("# Netscape HTTP Cookie File\n");
+        for cookie in self.cookies() {
+            text.push_str(&cookie.to_netscape_line());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Adds a cookie, replacing any with the same name, domain and path.
+    pub fn insert(&self, cookie: Cookie) {
+        let mut cookies = self.cookies.write().expect("cookie jar lock poisoned");
+        match cookies.iter_mut().find(|existing| existing.same_slot(&cookie)) {
+            Some(existing) => *existing = cookie,
+            None => cookies.push(cookie),
+        }
+    }
+
+    /// Value of the first cookie called `name`, whatever its domain.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.cookies.read().expect("cookie jar lock poisoned")
+            .iter()
+            .find(|cookie| cookie.name == name)
+            .map(|cookie| cookie.value.clone())
+    }
+
+    pub fn cookies(&self) -> Vec<Cookie> {
+        self.cookies.read().expect("cookie jar lock poisoned").clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cookies.read().expect("cookie jar lock poisoned").is_empty()
+    }
+
+    /// Name/value pairs to send to `url`, longest path first.
+    pub(crate) fn cookies_for(&self, url: &str, now: DateTime<Utc>) -> Vec<(String, String)> {
+        let Ok(url) = Url::parse(url) else {
+            return Vec::new();
+        };
+        let mut matching: Vec<Cookie> = self.cookies.read().expect("cookie jar lock poisoned")
+            .iter()
+            .filter(|cookie| !cookie.is_expired(now) && cookie.matches(&url))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        matching.into_iter().map(|cookie| (cookie.name, cookie.value)).collect()
+    }
+
+    /// Applies every `Set-Cookie` header of a response to the request sent to `url`.
+    pub(crate) fn store_response(&self, url: &str, headers: &[(String, String)], now: DateTime<Utc>) {
+        let Ok(url) = Url::parse(url) else {
+            return;
+        };
+        let set_cookies = headers.iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("set-cookie"))
+            .filter_map(|(_, value)| Cookie::from_set_cookie(value, &url, now));
+
+        for cookie in set_cookies {
+            if cookie.is_expired(now) {
+                // An already expired cookie is how servers delete one.
+                self.cookies.write().expect("cookie jar lock poisoned")
+                    .retain(|existing| !existing.same_slot(&cookie));
+            } else {
+                self.insert(cookie);
+            }
+        }
+    }
+}
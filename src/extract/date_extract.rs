@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::EstimatedTime;
+use super::YoutubeExtractor;
+use super::locale::{Locale, ENGLISH};
+
+impl YoutubeExtractor {
+
+    /// Resolves a comment's relative `published_time` against the instant its page was fetched.
+    pub(crate) fn parse_published_time(&self, text: &str, now: DateTime<Utc>) -> Option<EstimatedTime> {
+        self.date_locales().find_map(|locale| locale.parse_relative_time(text, now))
+    }
+
+    /// Whether a comment's published time carries the "(edited)" marker of the configured language or English.
+    pub(crate) fn is_edited(&self, published_time: &str) -> bool {
+        self.date_locales().any(|locale| locale.is_edited(published_time))
+    }
+
+    /// Resolves a video's `dateText`, which is relative for recent streams and premieres and a calendar date otherwise.
+    pub(crate) fn parse_upload_date(&self, text: &str, now: DateTime<Utc>) -> Option<EstimatedTime> {
+        self.date_locales().find_map(|locale| {
+            locale.parse_relative_time(text, now).or_else(|| locale.parse_date(text))
+        })
+    }
+
+    /// The configured language first, then English for responses that ignored `hl`.
+    fn date_locales(&self) -> impl Iterator<Item = &'static Locale> {
+        let configured = self.locale();
+        let fallback = (!std::ptr::eq(configured, &ENGLISH)).then_some(&ENGLISH);
+        std::iter::once(configured).chain(fallback)
+    }
+}
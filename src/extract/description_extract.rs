@@ -0,0 +1,170 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use reqwest::Url;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::models::{Chapter, DescriptionLink, DescriptionLinkKind, Thumbnail, VideoInfo};
+use super::YoutubeExtractor;
+
+const ATTRIBUTED_DESCRIPTION_PATH: &[&str] = &[
+    "contents", "twoColumnWatchNextResults", "results", "results", "contents",
+    "1", "videoSecondaryInfoRenderer", "attributedDescription"
+];
+
+/// A description line that starts with a `0:00` / `1:02:03` timestamp, optionally bulleted or bracketed.
+static DESCRIPTION_CHAPTER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*(?:[-•*]\s*)?[(\[]?((?:\d{1,2}:)?\d{1,2}:\d{2})[)\]]?\s*[-–—:|]?\s*(\S.*?)\s*$")
+        .expect("valid chapter regex")
+});
+
+impl YoutubeExtractor {
+
+    /// Fills in chapters and description links. Must run after the player response so the last chapter knows the video length.
+    pub(crate) fn apply_description(&self, initial_data: &Value, video_id: &str, video_info: &mut VideoInfo) {
+        let attributed_description = ATTRIBUTED_DESCRIPTION_PATH.iter()
+            .try_fold(initial_data, |value, key| match key.parse::<usize>() {
+                Ok(index) => value.get(index),
+                Err(_) => value.get(key),
+            })
+            .unwrap_or(&Value::Null);
+
+        video_info.description_links = get_description_links(attributed_description, video_id);
+
+        let mut chapters = self.get_panel_chapters(initial_data);
+        if chapters.is_empty() {
+            chapters = self.parse_description_chapters(&video_info.description);
+        }
+        set_chapter_ends(&mut chapters, video_info.duration_seconds);
+        debug!(chapters = chapters.len(), links = video_info.description_links.len(), "Extracted description metadata");
+        video_info.chapters = chapters;
+    }
+
+    /// Chapters from the `macroMarkersListRenderer` engagement panel, preferring creator chapters over auto-generated ones.
+    pub(crate) fn get_panel_chapters(&self, initial_data: &Value) -> Vec<Chapter> {
+        let panels = initial_data.get("engagementPanels").and_then(|p| p.as_array()).cloned().unwrap_or_default();
+
+        let marker_lists: Vec<(&str, &Vec<Value>)> = panels.iter()
+            .filter_map(|panel| {
+                let panel = panel.get("engagementPanelSectionListRenderer")?;
+                let identifier = panel.get("panelIdentifier").and_then(|i| i.as_str()).unwrap_or_default();
+                let items = panel.get("content")?.get("macroMarkersListRenderer")?.get("contents")?.as_array()?;
+                Some((identifier, items))
+            })
+            .collect();
+
+        let Some((_, items)) = marker_lists.iter()
+            .find(|(identifier, _)| identifier.contains("description-chapters"))
+            .or(marker_lists.first()) else {
+            return Vec::new();
+        };
+
+        items.iter()
+            .filter_map(|item| {
+                let item = item.get("macroMarkersListItemRenderer")?;
+                let start_seconds = item.get("onTap")
+                    .and_then(|tap| tap.get("watchEndpoint"))
+                    .and_then(|endpoint| endpoint.get("startTimeSeconds"))
+                    .and_then(|seconds| seconds.as_u64())
+                    .or_else(|| parse_clock(&self.get_text_from_path(item, &["timeDescription", "simpleText"])?))?;
+                Some(Chapter {
+                    title: self.get_text_from_path(item, &["title", "simpleText"])
+                        .or_else(|| self.get_text_from_path(item, &["title"]))
+                        .unwrap_or_default(),
+                    start_seconds,
+                    end_seconds: 0,
+                    thumbnail: self.get_thumbnails(item).into_iter().max_by_key(|t: &Thumbnail| t.width),
+                })
+            })
+            .collect()
+    }
+
+    /// Chapters from `0:00 Intro` lines, following YouTube's own rules: the first starts at 0:00,
+    /// there are at least three, and they run in order.
+    pub(crate) fn parse_description_chapters(&self, description: &str) -> Vec<Chapter> {
+        let chapters: Vec<Chapter> = DESCRIPTION_CHAPTER_PATTERN.captures_iter(description)
+            .filter_map(|caps| Some(Chapter {
+                title: caps[2].to_string(),
+                start_seconds: parse_clock(&caps[1])?,
+                ..Chapter::default()
+            }))
+            .collect();
+
+        let starts_at_zero = chapters.first().is_some_and(|c| c.start_seconds == 0);
+        let ascending = chapters.windows(2).all(|pair| pair[0].start_seconds < pair[1].start_seconds);
+        if !starts_at_zero || !ascending || chapters.len() < 3 {
+            return Vec::new();
+        }
+        chapters
+    }
+}
+
+/// Turns `attributedDescription.commandRuns` into typed links.
+pub(crate) fn get_description_links(attributed_description: &Value, video_id: &str) -> Vec<DescriptionLink> {
+    let content = attributed_description.get("content").and_then(|c| c.as_str()).unwrap_or_default();
+    // Run offsets count UTF-16 code units, not bytes or chars.
+    let utf16: Vec<u16> = content.encode_utf16().collect();
+
+    let Some(runs) = attributed_description.get("commandRuns").and_then(|r| r.as_array()) else {
+        return Vec::new();
+    };
+
+    runs.iter()
+        .filter_map(|run| {
+            let start = run.get("startIndex")?.as_u64()? as usize;
+            let length = run.get("length")?.as_u64()? as usize;
+            let text = String::from_utf16_lossy(utf16.get(start..start.checked_add(length)?)?);
+            let command = run.get("onTap")?.get("innertubeCommand")?;
+
+            let watch_endpoint = command.get("watchEndpoint");
+            let start_seconds = watch_endpoint
+                .filter(|endpoint| endpoint.get("videoId").and_then(|v| v.as_str()) == Some(video_id))
+                .and_then(|endpoint| endpoint.get("startTimeSeconds"))
+                .and_then(|seconds| seconds.as_u64());
+
+            let kind = if start_seconds.is_some() {
+                DescriptionLinkKind::Timestamp
+            } else if text.starts_with('#') {
+                DescriptionLinkKind::Hashtag
+            } else if text.starts_with('@') {
+                DescriptionLinkKind::Mention
+            } else {
+                DescriptionLinkKind::Url
+            };
+
+            Some(DescriptionLink { kind, text, url: command_url(command)?, start_seconds })
+        })
+        .collect()
+}
+
+fn command_url(command: &Value) -> Option<String> {
+    let url = command.get("urlEndpoint")
+        .and_then(|endpoint| endpoint.get("url"))
+        .or_else(|| command.get("commandMetadata")?.get("webCommandMetadata")?.get("url"))?
+        .as_str()?;
+
+    let url = Url::parse("https://www.youtube.com").ok()?.join(url).ok()?;
+    // External links are wrapped as youtube.com/redirect?q=<target>.
+    if url.path() == "/redirect"
+        && let Some((_, target)) = url.query_pairs().find(|(key, _)| key == "q") {
+        return Some(target.into_owned());
+    }
+    Some(url.to_string())
+}
+
+fn set_chapter_ends(chapters: &mut [Chapter], duration_seconds: u64) {
+    let next_starts: Vec<u64> = chapters.iter().skip(1).map(|c| c.start_seconds).collect();
+    for (i, chapter) in chapters.iter_mut().enumerate() {
+        chapter.end_seconds = next_starts.get(i)
+            .copied()
+            .unwrap_or_else(|| duration_seconds.max(chapter.start_seconds));
+    }
+}
+
+/// Parses `1:05` or `1:02:03` into seconds.
+fn parse_clock(text: &str) -> Option<u64> {
+    text.trim()
+        .split(':')
+        .try_fold(0u64, |total, part| total.checked_mul(60)?.checked_add(part.parse().ok()?))
+}
@@ -1,22 +1,64 @@
-#[derive(Debug)]
-pub enum YoutubeError {
-    ApiKeyNotFound,
-    ApiRequestError(Box<dyn std::error::Error>),
-}
+use std::time::Duration;
 
-impl From<Box<dyn std::error::Error>> for YoutubeError {
-    fn from(error: Box<dyn std::error::Error>) -> Self {
-        YoutubeError::ApiRequestError(error)
-    }
-}
+use serde::{Deserialize, Serialize};
 
-impl std::fmt::Display for YoutubeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            YoutubeError::ApiKeyNotFound => write!(f, "🩻🩻 API key not found in YouTube config.. 🩻🩻"),
-            YoutubeError::ApiRequestError(e) => write!(f, "🩻🩻 The API request for comment data failed. {} 🩻🩻", e),
-        }
-    }
+/// The kind of page YouTube served instead of content when it suspected automated traffic.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotCheckKind {
+    /// A reCAPTCHA challenge, usually on google.com/sorry.
+    Captcha,
+    /// The watch page's "Sign in to confirm you're not a bot" wall.
+    SignInWall,
+    /// The "unusual traffic from your computer network" notice, without a challenge.
+    UnusualTraffic,
+    /// An HTML "Too Many Requests" page rather than a bare 429.
+    ///
+    /// Only seen on [`YoutubeError::RateLimited`], as are other bot checks served with a 429.
+    RateLimitPage,
 }
 
-impl std::error::Error for YoutubeError {}
+#[derive(Debug, thiserror::Error)]
+pub enum YoutubeError {
+    #[error("🩻🩻 API key not found in YouTube config.. 🩻🩻")]
+    ApiKeyNotFound,
+    #[error("🩻🩻 Could not find a video ID in {0:?} 🩻🩻")]
+    InvalidVideoId(String),
+    #[error("🩻🩻 Not a recognised YouTube URL: {0:?} 🩻🩻")]
+    UnrecognizedUrl(String),
+    #[error("🩻🩻 Video is unavailable: {reason} 🩻🩻")]
+    VideoUnavailable { reason: String },
+    #[error("🩻🩻 Video is age restricted: {reason} 🩻🩻")]
+    AgeRestricted { reason: String },
+    #[error("🩻🩻 Video is private 🩻🩻")]
+    Private,
+    #[error("🩻🩻 YouTube served a cookie consent page instead of the video 🩻🩻")]
+    ConsentRequired,
+    #[error("🩻🩻 YouTube served a bot check ({kind:?}) at {url} 🩻🩻")]
+    BotCheck { kind: BotCheckKind, url: String },
+    #[error("🩻🩻 Rate limited by YouTube (retry after {retry_after:?}) 🩻🩻")]
+    RateLimited {
+        retry_after: Option<Duration>,
+        /// What the 429 page showed, when it was a captcha or an HTML error page rather than a bare status.
+        bot_check: Option<BotCheckKind>,
+    },
+    #[error("🩻🩻 Could not extract ytInitialData from the watch page 🩻🩻")]
+    InitialDataMissing,
+    #[error("🩻🩻 YouTube response layout changed, nothing found at {path} 🩻🩻")]
+    SchemaChanged { path: String },
+    #[error("🩻🩻 Comments are turned off for this video 🩻🩻")]
+    CommentsDisabled,
+    #[error("🩻🩻 YouTube responded with HTTP {status} 🩻🩻")]
+    Http { status: u16 },
+    #[error("🩻🩻 The request to YouTube failed. {0} 🩻🩻")]
+    Network(#[from] reqwest::Error),
+    #[error("🩻🩻 Failed to parse JSON. {0} 🩻🩻")]
+    Json(#[from] serde_json::Error),
+    #[error("🩻🩻 Not a usable proxy URL: {url:?} 🩻🩻")]
+    InvalidProxy { url: String },
+    #[error("🩻🩻 Invalid extractor configuration: {reason} 🩻🩻")]
+    InvalidConfig { reason: String },
+    #[error("🩻🩻 No recorded response for cassette key {key} 🩻🩻")]
+    CassetteMiss { key: String },
+    #[error("🩻🩻 I/O error. {0} 🩻🩻")]
+    Io(#[from] std::io::Error),
+}
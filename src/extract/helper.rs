@@ -1,7 +1,102 @@
+use serde_json::Value;
+
 pub fn string_to_bool(value: &str) -> Option<bool> {
     match value.trim().to_lowercase().as_str() {
         "true" | "yes" | "y" | "1" | "on" => Some(true),
         "false" | "no" | "n" | "0" | "off" => Some(false),
         _ => None,
     }
-}
\ No newline at end of file
+}
+
+/// 64-bit FNV-1a. Stable across platforms and Rust versions, unlike `DefaultHasher`.
+pub fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+pub fn get_text_from_path(data: &Value, path: &[&str]) -> Option<String> {
+    let mut current = data;
+
+    for key in path {
+        current = if key.chars().all(char::is_numeric) {
+            let index: usize = key.parse().ok()?;
+            current.as_array()?.get(index)?
+        } else {
+            current.get(key)?
+        }
+    }
+
+    if let Some(s) = current.as_str() {
+        return Some(s.to_string());
+    }
+
+    if let Some(b) = current.as_bool() {
+        return Some(b.to_string());
+    }
+
+    if let Some(runs) = current.get("runs").and_then(|r| r.as_array()) {
+        let text: String = runs
+            .iter()
+            .filter_map(|run| run.get("text")?.as_str())
+            .collect();
+        if !text.is_empty() {
+            return Some(text);
+        }
+    }
+    None
+}
+
+/// Finds `name = {...}` (or `window["name"] = {...}`) in the page and parses the object.
+pub fn extract_json_variable(webpage: &str, name: &str) -> Option<Value> {
+    let patterns = [
+        format!("window[\"{name}\"] = "),
+        format!("window['{name}'] = "),
+        format!("{name} = "),
+        format!("var {name} = "),
+    ];
+
+    for pattern in &patterns {
+        if let Some(start) = webpage.find(pattern.as_str()) {
+            let json_start = start + pattern.len();
+
+            if let Some(end) = find_json_end(&webpage[json_start..]) {
+                let json_str = &webpage[json_start..json_start + end];
+
+                match serde_json::from_str::<Value>(json_str) {
+                    Ok(data) => return Some(data),
+                    Err(_) => continue,
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Returns the byte length of the JSON object that `text` starts with, tracking string escapes.
+pub fn find_json_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, ch) in text.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match ch {
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            },
+            _ => {}
+        }
+    }
+    None
+}
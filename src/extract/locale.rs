@@ -0,0 +1,322 @@
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Months, NaiveDate, TimeDelta, Utc};
+use regex::Regex;
+
+use crate::models::{EstimatedTime, TimePrecision};
+
+/// A number with optional digit grouping: `799,831`, `10.464`, `1 234 567`, `12,34,567`, `1,2`.
+static NUMBER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\d+(?:[.,'’]\d+|[ \u{a0}\u{202f}]\d{3}\b)*").expect("valid number regex")
+});
+
+/// `2025/08/23`, `2025-08-23`, `2025年8月23日`.
+static YEAR_FIRST_DATE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d{4})\s*[/年.\-]\s*(\d{1,2})\s*[/月.\-]\s*(\d{1,2})").expect("valid date regex")
+});
+
+/// `23.08.2025`, `23/08/2025`, or `8/23/2025` for month-first locales.
+static YEAR_LAST_DATE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(\d{1,2})[./](\d{1,2})[./](\d{4})\b").expect("valid date regex")
+});
+
+/// The words and abbreviations YouTube uses for numbers and dates in one interface language.
+pub(crate) struct Locale {
+    pub language: &'static str,
+    /// Abbreviation suffixes and what they multiply by, e.g. `("mio.", 1_000_000)`. Lowercase.
+    suffixes: &'static [(&'static str, u64)],
+    /// Marks a relative time as being in the past, e.g. "ago" or "vor". Lowercase.
+    ago: &'static [&'static str],
+    /// Words standing in for the number 1, as in "a day ago". Lowercase.
+    one: &'static [&'static str],
+    /// Stems of time unit words, e.g. "tag" matches "Tag" and "Tagen". Lowercase.
+    units: &'static [(&'static str, TimePrecision)],
+    /// Stems of month names, January first. Lowercase.
+    months: [&'static [&'static str]; 12],
+    /// Numeric dates are written month first, as in `8/23/2025`.
+    month_first: bool,
+    /// The marker appended to an edited comment's published time, e.g. "(edited)". Lowercase, without brackets.
+    edited: &'static [&'static str],
+}
+
+/// Abbreviations geo-routed or untranslated responses fall back to, whatever the configured language.
+const ENGLISH_SUFFIXES: &[(&str, u64)] = &[
+    ("crore", 10_000_000), ("lakh", 100_000),
+    ("k", 1_000), ("m", 1_000_000), ("b", 1_000_000_000),
+];
+
+pub(crate) static ENGLISH: Locale = Locale {
+    language: "en",
+    suffixes: &[],
+    ago: &["ago"],
+    one: &["a", "an", "one"],
+    units: &[
+        ("second", TimePrecision::Seconds), ("minute", TimePrecision::Minutes), ("hour", TimePrecision::Hours),
+        ("day", TimePrecision::Days), ("week", TimePrecision::Weeks), ("month", TimePrecision::Months),
+        ("year", TimePrecision::Years),
+    ],
+    months: [&["jan"], &["feb"], &["mar"], &["apr"], &["may"], &["jun"], &["jul"], &["aug"], &["sep"], &["oct"], &["nov"], &["dec"]],
+    month_first: true,
+    edited: &["edited"],
+};
+
+static GERMAN: Locale = Locale {
+    language: "de",
+    suffixes: &[("tsd.", 1_000), ("mio.", 1_000_000), ("mrd.", 1_000_000_000)],
+    ago: &["vor"],
+    one: &["einem", "einer"],
+    units: &[
+        ("sekunde", TimePrecision::Seconds), ("minute", TimePrecision::Minutes), ("stunde", TimePrecision::Hours),
+        ("tag", TimePrecision::Days), ("woche", TimePrecision::Weeks), ("monat", TimePrecision::Months),
+        ("jahr", TimePrecision::Years),
+    ],
+    months: [&["jan"], &["feb"], &["märz", "mär"], &["apr"], &["mai"], &["jun"], &["jul"], &["aug"], &["sep"], &["okt"], &["nov"], &["dez"]],
+    month_first: false,
+    edited: &["bearbeitet"],
+};
+
+static SPANISH: Locale = Locale {
+    language: "es",
+    suffixes: &[("mil", 1_000), ("m", 1_000_000)],
+    ago: &["hace"],
+    one: &["un", "una"],
+    units: &[
+        ("segundo", TimePrecision::Seconds), ("minuto", TimePrecision::Minutes), ("hora", TimePrecision::Hours),
+        ("día", TimePrecision::Days), ("dia", TimePrecision::Days), ("semana", TimePrecision::Weeks),
+        ("mes", TimePrecision::Months), ("año", TimePrecision::Years),
+    ],
+    months: [&["ene"], &["feb"], &["mar"], &["abr"], &["may"], &["jun"], &["jul"], &["ago"], &["sep"], &["oct"], &["nov"], &["dic"]],
+    month_first: false,
+    edited: &["editado"],
+};
+
+static PORTUGUESE: Locale = Locale {
+    language: "pt",
+    suffixes: &[("mil", 1_000), ("mi", 1_000_000), ("bi", 1_000_000_000)],
+    ago: &["há"],
+    one: &["um", "uma"],
+    units: &[
+        ("segundo", TimePrecision::Seconds), ("minuto", TimePrecision::Minutes), ("hora", TimePrecision::Hours),
+        ("dia", TimePrecision::Days), ("semana", TimePrecision::Weeks), ("mês", TimePrecision::Months),
+        ("mes", TimePrecision::Months), ("ano", TimePrecision::Years),
+    ],
+    months: [&["jan"], &["fev"], &["mar"], &["abr"], &["mai"], &["jun"], &["jul"], &["ago"], &["set"], &["out"], &["nov"], &["dez"]],
+    month_first: false,
+    edited: &["editado"],
+};
+
+static FRENCH: Locale = Locale {
+    language: "fr",
+    suffixes: &[("md", 1_000_000_000), ("k", 1_000), ("m", 1_000_000)],
+    ago: &["il y a"],
+    one: &["un", "une"],
+    units: &[
+        ("seconde", TimePrecision::Seconds), ("minute", TimePrecision::Minutes), ("heure", TimePrecision::Hours),
+        ("jour", TimePrecision::Days), ("semaine", TimePrecision::Weeks), ("mois", TimePrecision::Months),
+        ("an", TimePrecision::Years),
+    ],
+    months: [&["janv"], &["févr"], &["mars"], &["avr"], &["mai"], &["juin"], &["juil"], &["août"], &["sept"], &["oct"], &["nov"], &["déc"]],
+    month_first: false,
+    edited: &["modifié"],
+};
+
+static RUSSIAN: Locale = Locale {
+    language: "ru",
+    suffixes: &[("тыс.", 1_000), ("млн", 1_000_000), ("млрд", 1_000_000_000)],
+    ago: &["назад"],
+    one: &[],
+    units: &[
+        ("секунд", TimePrecision::Seconds), ("минут", TimePrecision::Minutes), ("час", TimePrecision::Hours),
+        ("день", TimePrecision::Days), ("дня", TimePrecision::Days), ("дней", TimePrecision::Days),
+        ("недел", TimePrecision::Weeks), ("месяц", TimePrecision::Months),
+        ("год", TimePrecision::Years), ("лет", TimePrecision::Years),
+    ],
+    months: [&["янв"], &["фев"], &["мар"], &["апр"], &["мая", "май"], &["июн"], &["июл"], &["авг"], &["сен"], &["окт"], &["ноя"], &["дек"]],
+    month_first: false,
+    edited: &["изменено"],
+};
+
+static JAPANESE: Locale = Locale {
+    language: "ja",
+    suffixes: &[("千", 1_000), ("万", 10_000), ("億", 100_000_000)],
+    ago: &["前"],
+    one: &[],
+    units: &[
+        ("秒", TimePrecision::Seconds), ("分", TimePrecision::Minutes), ("時間", TimePrecision::Hours),
+        ("日", TimePrecision::Days), ("週間", TimePrecision::Weeks), ("か月", TimePrecision::Months),
+        ("ヶ月", TimePrecision::Months), ("カ月", TimePrecision::Months), ("年", TimePrecision::Years),
+    ],
+    months: [&[]; 12],
+    month_first: false,
+    edited: &["編集済み"],
+};
+
+static CHINESE: Locale = Locale {
+    language: "zh",
+    suffixes: &[("千", 1_000), ("万", 10_000), ("萬", 10_000), ("亿", 100_000_000), ("億", 100_000_000)],
+    ago: &["前"],
+    one: &[],
+    units: &[
+        ("秒", TimePrecision::Seconds), ("分钟", TimePrecision::Minutes), ("分鐘", TimePrecision::Minutes),
+        ("小时", TimePrecision::Hours), ("小時", TimePrecision::Hours), ("天", TimePrecision::Days),
+        ("周", TimePrecision::Weeks), ("週", TimePrecision::Weeks), ("星期", TimePrecision::Weeks),
+        ("个月", TimePrecision::Months), ("個月", TimePrecision::Months), ("年", TimePrecision::Years),
+    ],
+    months: [&[]; 12],
+    month_first: false,
+    edited: &["已编辑", "已編輯", "已修改"],
+};
+
+static HINDI: Locale = Locale {
+    language: "hi",
+    suffixes: &[("हज़ार", 1_000), ("लाख", 100_000), ("करोड़", 10_000_000)],
+    ago: &["पहले"],
+    one: &[],
+    units: &[
+        ("सेकंड", TimePrecision::Seconds), ("मिनट", TimePrecision::Minutes), ("घंट", TimePrecision::Hours),
+        ("दिन", TimePrecision::Days), ("हफ़्त", TimePrecision::Weeks), ("सप्ताह", TimePrecision::Weeks),
+        ("महीन", TimePrecision::Months), ("साल", TimePrecision::Years), ("वर्ष", TimePrecision::Years),
+    ],
+    months: [&["जन"], &["फ़र"], &["मार्च"], &["अप्रैल"], &["मई"], &["जून"], &["जुल"], &["अग"], &["सित"], &["अक्तू"], &["नव"], &["दिस"]],
+    month_first: false,
+    edited: &["बदलाव किया गया", "संपादित"],
+};
+
+static LOCALES: [&Locale; 9] = [&ENGLISH, &GERMAN, &SPANISH, &PORTUGUESE, &FRENCH, &RUSSIAN, &JAPANESE, &CHINESE, &HINDI];
+
+/// A count as YouTube displays it. `exact` is false for abbreviations like "1.2K".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DisplayCount {
+    pub value: u64,
+    pub exact: bool,
+}
+
+/// The locale for an `hl` code such as `de` or `pt-BR`, English if it isn't supported.
+pub(crate) fn for_language(language: &str) -> &'static Locale {
+    let primary = language.split(['-', '_']).next().unwrap_or_default().to_lowercase();
+    LOCALES.iter().find(|locale| locale.language == primary).copied().unwrap_or(&ENGLISH)
+}
+
+impl Locale {
+
+    /// Whether a comment's published time, such as "vor 1 Tag (bearbeitet)", marks it as edited.
+    pub(crate) fn is_edited(&self, published_time: &str) -> bool {
+        let text = published_time.to_lowercase();
+        self.edited.iter().any(|marker| text.contains(marker))
+    }
+
+    /// Parses the first number in `text`, e.g. "799,831 views", "1,2 Mio. Aufrufe" or "12万 回視聴".
+    pub(crate) fn parse_count(&self, text: &str) -> Option<DisplayCount> {
+        let number = NUMBER_PATTERN.find(text)?;
+        let rest = text[number.end()..].trim_start().to_lowercase();
+
+        let Some(multiplier) = self.suffixes.iter()
+            .chain(ENGLISH_SUFFIXES)
+            .find(|(suffix, _)| starts_with_word(&rest, suffix))
+            .map(|(_, multiplier)| *multiplier) else {
+            // Unabbreviated counts are whole numbers, so every separator groups digits.
+            let digits: String = number.as_str().chars().filter(char::is_ascii_digit).collect();
+            return Some(DisplayCount { value: digits.parse().ok()?, exact: true });
+        };
+
+        // Abbreviations keep at most one decimal separator, whichever character the locale uses.
+        let mantissa = number.as_str();
+        let (whole, fraction) = match mantissa.rfind(['.', ',']) {
+            Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+            None => (mantissa, ""),
+        };
+        let whole: String = whole.chars().filter(char::is_ascii_digit).collect();
+        let value: f64 = format!("{whole}.{fraction}0").parse().ok()?;
+        Some(DisplayCount { value: (value * multiplier as f64).round() as u64, exact: false })
+    }
+
+    /// Resolves "21 hours ago", "vor 2 Tagen", "hace 1 mes", "3 日前" and the like against `now`.
+    pub(crate) fn parse_relative_time(&self, text: &str, now: DateTime<Utc>) -> Option<EstimatedTime> {
+        let text = text.to_lowercase();
+        if !self.ago.iter().any(|marker| text.contains(marker)) {
+            return None;
+        }
+
+        let numbers = NUMBER_PATTERN.find_iter(&text).map(|m| (m.as_str().parse::<u32>().ok(), m.end()));
+        let words = self.one.iter()
+            .flat_map(|word| {
+                let with_space = format!("{word} ");
+                text.match_indices(&with_space).map(|(index, _)| (index, index + word.len())).collect::<Vec<_>>()
+            })
+            .filter(|(index, _)| is_word_start(&text, *index))
+            .map(|(_, end)| (Some(1), end));
+
+        numbers.chain(words).find_map(|(amount, end)| {
+            let rest = text[end..].trim_start();
+            let (_, precision) = self.units.iter().find(|(stem, _)| rest.starts_with(stem))?;
+            Some(EstimatedTime { timestamp: subtract(now, amount?, *precision)?, precision: *precision })
+        })
+    }
+
+    /// Parses a calendar date: "Aug 23, 2025", "23.08.2025", "23 de ago. de 2025", "2025/08/23".
+    /// Dates carry no time of day, so they resolve to midnight UTC.
+    pub(crate) fn parse_date(&self, text: &str) -> Option<EstimatedTime> {
+        let (year, month, day) = self.parse_ymd(&text.to_lowercase())?;
+        let date = NaiveDate::from_ymd_opt(year, month, day)?;
+        Some(EstimatedTime {
+            timestamp: date.and_hms_opt(0, 0, 0)?.and_utc(),
+            precision: TimePrecision::Days,
+        })
+    }
+
+    fn parse_ymd(&self, text: &str) -> Option<(i32, u32, u32)> {
+        if let Some(caps) = YEAR_FIRST_DATE_PATTERN.captures(text) {
+            return Some((caps[1].parse().ok()?, caps[2].parse().ok()?, caps[3].parse().ok()?));
+        }
+        if let Some(caps) = YEAR_LAST_DATE_PATTERN.captures(text) {
+            let (first, second) = (caps[1].parse().ok()?, caps[2].parse().ok()?);
+            let (month, day) = if self.month_first { (first, second) } else { (second, first) };
+            return Some((caps[3].parse().ok()?, month, day));
+        }
+
+        // A month name plus a day and a four digit year, in any order.
+        let month = self.months.iter()
+            .enumerate()
+            .filter_map(|(index, stems)| {
+                let position = stems.iter()
+                    .flat_map(|stem| text.match_indices(stem))
+                    .map(|(position, _)| position)
+                    .find(|position| is_word_start(text, *position))?;
+                Some((position, index as u32 + 1))
+            })
+            .min()
+            .map(|(_, month)| month)?;
+        let numbers: Vec<&str> = NUMBER_PATTERN.find_iter(text).map(|m| m.as_str()).collect();
+        let year = numbers.iter().find(|n| n.len() == 4)?.parse().ok()?;
+        let day = numbers.iter().find(|n| n.len() <= 2)?.parse().ok()?;
+        Some((year, month, day))
+    }
+}
+
+/// `rest` starts with `word`, not followed by more letters of the same word: "k" matches "k views"
+/// but not "kommentare". Scripts written without spaces, like 万回視聴, need no boundary.
+fn starts_with_word(rest: &str, word: &str) -> bool {
+    let Some(after) = rest.strip_prefix(word) else {
+        return false;
+    };
+    let needs_boundary = word.chars().last().is_some_and(|c| c.is_alphabetic() && (c as u32) < 0x3000);
+    !needs_boundary || !after.chars().next().is_some_and(char::is_alphabetic)
+}
+
+fn is_word_start(text: &str, index: usize) -> bool {
+    !text[..index].chars().next_back().is_some_and(char::is_alphabetic)
+}
+
+fn subtract(now: DateTime<Utc>, amount: u32, precision: TimePrecision) -> Option<DateTime<Utc>> {
+    let amount = i64::from(amount);
+    match precision {
+        TimePrecision::Seconds => now.checked_sub_signed(TimeDelta::try_seconds(amount)?),
+        TimePrecision::Minutes => now.checked_sub_signed(TimeDelta::try_minutes(amount)?),
+        TimePrecision::Hours => now.checked_sub_signed(TimeDelta::try_hours(amount)?),
+        TimePrecision::Days => now.checked_sub_signed(TimeDelta::try_days(amount)?),
+        TimePrecision::Weeks => now.checked_sub_signed(TimeDelta::try_weeks(amount)?),
+        // Calendar months, so "1 month ago" on March 31st is February's last day.
+        TimePrecision::Months => now.checked_sub_months(Months::new(amount.try_into().ok()?)),
+        TimePrecision::Years => now.checked_sub_months(Months::new(u32::try_from(amount).ok()?.checked_mul(12)?)),
+    }
+}
@@ -1,6 +1,33 @@
 pub mod youtube_extractor;
 pub mod comment_extract;
+pub mod comment_stream;
+pub mod player_extract;
+pub mod caption_extract;
+pub mod description_extract;
+pub mod date_extract;
+pub mod bot_check;
 pub mod error_msgs;
+pub mod config;
+pub mod transport;
+pub mod cassette;
+pub mod url;
+pub mod rate_limit;
+pub mod retry;
+pub mod clock;
+pub mod cookies;
+pub mod proxy;
+pub mod raw;
 mod helper;
+mod locale;
 
-pub use youtube_extractor::YoutubeExtractor;
\ No newline at end of file
+pub use youtube_extractor::YoutubeExtractor;
+pub use config::{CommentOptions, CommentSort, ExtractorBuilder, ExtractorConfig};
+pub use transport::{HttpResponse, ReqwestTransport, Transport};
+pub use rate_limit::RateLimiter;
+pub use retry::RetryPolicy;
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use cookies::{Cookie, CookieJar};
+pub use proxy::{ProxyPool, ProxyStats, Rotation};
+pub use cassette::{RecordingTransport, ReplayTransport};
+pub use url::{ChannelRef, VideoId, YoutubeUrl};
+pub use comment_stream::{CrawlCheckpoint, CrawlEvent, PendingItem};
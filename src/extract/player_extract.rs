@@ -0,0 +1,109 @@
+use serde_json::Value;
+use tracing::debug;
+
+use crate::models::{PlayabilityStatus, Thumbnail, VideoInfo};
+use super::YoutubeExtractor;
+use super::error_msgs::YoutubeError;
+
+impl YoutubeExtractor {
+
+    pub(crate) fn extract_player_response(&self, webpage: &str) -> Option<Value> {
+        self.extract_json_variable(webpage, "ytInitialPlayerResponse")
+    }
+
+    pub(crate) fn get_playability_status(&self, player_response: &Value) -> PlayabilityStatus {
+        PlayabilityStatus {
+            status: self.get_text_from_path(player_response, &["playabilityStatus", "status"]).unwrap_or_default(),
+            reason: self.get_text_from_path(player_response, &["playabilityStatus", "reason"]),
+        }
+    }
+
+    /// Turns a non-playable `playabilityStatus` into an error.
+    pub(crate) fn check_playability(&self, player_response: Option<&Value>) -> Result<(), YoutubeError> {
+        let Some(player_response) = player_response else {
+            debug!("No ytInitialPlayerResponse found, skipping playability check");
+            return Ok(());
+        };
+
+        let PlayabilityStatus { status, reason } = self.get_playability_status(player_response);
+        let reason = reason.unwrap_or_default();
+        let lower_reason = reason.to_lowercase();
+        // `videoDetails.isPrivate` is only missing from some responses, which fall back to the English wording.
+        let private = player_response.pointer("/videoDetails/isPrivate").and_then(Value::as_bool) == Some(true)
+            || lower_reason.contains("private");
+        // Age gates carry `desktopLegacyAgeGateReason` whatever the language; the wording is a fallback.
+        let age_gate = player_response.pointer("/playabilityStatus/desktopLegacyAgeGateReason").is_some()
+            || lower_reason.contains("confirm your age");
+
+        match status.as_str() {
+            "" | "OK" | "LIVE_STREAM_OFFLINE" => Ok(()),
+            "AGE_CHECK_REQUIRED" | "AGE_VERIFICATION_REQUIRED" | "CONTENT_CHECK_REQUIRED" => {
+                Err(YoutubeError::AgeRestricted { reason })
+            }
+            "LOGIN_REQUIRED" if private => Err(YoutubeError::Private),
+            "LOGIN_REQUIRED" if age_gate => Err(YoutubeError::AgeRestricted { reason }),
+            _ => Err(YoutubeError::VideoUnavailable { reason }),
+        }
+    }
+
+    /// Fills in the fields only found in `ytInitialPlayerResponse`.
+    pub(crate) fn apply_player_response(&self, player_response: &Value, video_info: &mut VideoInfo) {
+        let details = player_response.get("videoDetails").unwrap_or(&Value::Null);
+        let microformat = player_response
+            .get("microformat")
+            .and_then(|m| m.get("playerMicroformatRenderer"))
+            .unwrap_or(&Value::Null);
+
+        video_info.playability = self.get_playability_status(player_response);
+
+        video_info.duration_seconds = self.get_text_from_path(details, &["lengthSeconds"])
+            .or_else(|| self.get_text_from_path(microformat, &["lengthSeconds"]))
+            .and_then(|seconds| seconds.parse().ok())
+            .unwrap_or_default();
+
+        // The watch page shows "1.2M views" or "1,234 watching now"; the player has the exact total.
+        if let Some(views) = self.get_text_from_path(details, &["viewCount"])
+            .and_then(|views| views.parse().ok()) {
+            video_info.views = views;
+        }
+
+        video_info.keywords = self.get_string_list(details, "keywords");
+        video_info.category = self.get_text_from_path(microformat, &["category"]).unwrap_or_default();
+        video_info.is_live_content = self.get_flag(details, "isLiveContent");
+        video_info.is_private = self.get_flag(details, "isPrivate");
+        video_info.is_unlisted = self.get_flag(microformat, "isUnlisted");
+        video_info.is_family_safe = self.get_flag(microformat, "isFamilySafe");
+        video_info.available_countries = self.get_string_list(microformat, "availableCountries");
+        video_info.publish_date_iso = self.get_text_from_path(microformat, &["publishDate"]);
+        video_info.upload_date_iso = self.get_text_from_path(microformat, &["uploadDate"]);
+        video_info.thumbnails = self.get_thumbnails(details);
+        video_info.caption_tracks = self.get_caption_tracks(player_response);
+    }
+
+    fn get_flag(&self, data: &Value, key: &str) -> bool {
+        data.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+    }
+
+    fn get_string_list(&self, data: &Value, key: &str) -> Vec<String> {
+        data.get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|item| item.as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get_thumbnails(&self, data: &Value) -> Vec<Thumbnail> {
+        data.get("thumbnail")
+            .and_then(|t| t.get("thumbnails"))
+            .and_then(|t| t.as_array())
+            .map(|thumbnails| {
+                thumbnails.iter()
+                    .filter_map(|thumbnail| Some(Thumbnail {
+                        url: thumbnail.get("url")?.as_str()?.to_string(),
+                        width: thumbnail.get("width").and_then(|w| w.as_u64()).unwrap_or(0) as u32,
+                        height: thumbnail.get("height").and_then(|h| h.as_u64()).unwrap_or(0) as u32,
+                    }))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
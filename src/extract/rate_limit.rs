@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// A token bucket spacing out requests to YouTube, even when several run at once.
+///
+/// Every clone of a [`YoutubeExtractor`](super::YoutubeExtractor) shares one limiter; pass the
+/// same `Arc` to [`ExtractorBuilder::rate_limiter`](super::ExtractorBuilder::rate_limiter) to
+/// share it between extractors too.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    burst: u32,
+    /// When the bucket will be back to full if no more requests are made.
+    full_at: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// Allows one request per `interval`. `Duration::ZERO` disables limiting.
+    pub fn new(interval: Duration) -> Self {
+        Self::with_burst(interval, 1)
+    }
+
+    /// Refills one token per `interval` into a bucket holding `burst` tokens, so up to `burst`
+    /// requests go out at once after a quiet period. Values below 1 are treated as 1.
+    pub fn with_burst(interval: Duration, burst: u32) -> Self {
+        Self { interval, burst: burst.max(1), full_at: Mutex::new(None) }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Waits until the next request may start.
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut full_at = self.full_at.lock().await;
+            let now = Instant::now();
+            if self.interval.is_zero() && full_at.is_none_or(|full_at| full_at <= now) {
+                return;
+            }
+            let full = full_at.map_or(now, |full_at| full_at.max(now));
+            let slot = full.checked_sub(self.bucket_span()).map_or(now, |slot| slot.max(now));
+            *full_at = Some(full + self.interval);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+
+    /// Empties the bucket and holds every caller back for `duration`, e.g. after a `Retry-After`.
+    pub async fn pause_for(&self, duration: Duration) {
+        let mut full_at = self.full_at.lock().await;
+        let resume = Instant::now() + duration + self.bucket_span();
+        *full_at = Some(full_at.map_or(resume, |full_at| full_at.max(resume)));
+    }
+
+    /// How long a full bucket takes to drain at one request per `interval`.
+    fn bucket_span(&self) -> Duration {
+        self.interval * (self.burst - 1)
+    }
+}
@@ -0,0 +1,97 @@
+//! Low-level building blocks behind [`YoutubeExtractor::extract`].
+//!
+//! These expose the individual innertube requests and JSON helpers the extractor
+//! is made of. They follow YouTube's internal response layout closely and may
+//! change between releases; prefer the top-level API where possible.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::models::{Chapter, Comment, CommentContent, DescriptionLink, EstimatedTime};
+use super::{bot_check, description_extract, helper, YoutubeExtractor};
+use super::error_msgs::{BotCheckKind, YoutubeError};
+use super::transport::HttpResponse;
+
+/// Sends a single `youtubei/v1/next` request for the given continuation token.
+pub async fn comments_request(extractor: &YoutubeExtractor, api_key: &String, continuation: &String) -> Result<Value, YoutubeError> {
+    extractor.comments_request(api_key, continuation, &0).await.map(|(data, _)| data)
+}
+
+/// Parses one comments page and fetches the replies of every thread on it, replies first.
+///
+/// `request_count` is the 1-based page number; the first page uses a different response layout.
+pub async fn comment_extractor(extractor: &YoutubeExtractor, data: &Value, api_key: &String, video_id: &str, request_count: usize) -> Result<Vec<Comment>, YoutubeError> {
+    extractor.comment_extractor(data, api_key, video_id, &request_count).await
+}
+
+/// Fetches the replies of a single comment thread from its reply continuation token.
+pub async fn reply_extractor(extractor: &YoutubeExtractor, api_key: &String, continuation_token: &str, reply_count: &u64, comment_id: &String, video_id: &str) -> Result<Vec<Comment>, YoutubeError> {
+    extractor.reply_extractor(api_key, continuation_token, reply_count, comment_id, video_id).await
+}
+
+/// Parses one `frameworkUpdates.entityBatchUpdate.mutations` entry into its comment fields.
+///
+/// `fetched_at` is when the response holding the entry was received; pass the same instant for every entry of a page.
+pub async fn get_comment_info(extractor: &YoutubeExtractor, comment_content_json: &Value, video_id: &str, fetched_at: DateTime<Utc>) -> Option<CommentContent> {
+    extractor.get_comment_info(comment_content_json, video_id, fetched_at).await
+}
+
+/// Pulls the `ytcfg` configuration object out of a watch page.
+pub async fn extract_ytcfg(extractor: &YoutubeExtractor, webpage: &str) -> Result<Value, YoutubeError> {
+    extractor.extract_ytcfg(webpage).await
+}
+
+/// Walks `path` through `data` and returns the text found there.
+///
+/// Numeric path segments index into arrays. Strings, booleans and `runs` text
+/// arrays are all flattened into a `String`.
+pub fn get_text_from_path(data: &Value, path: &[&str]) -> Option<String> {
+    helper::get_text_from_path(data, path)
+}
+
+/// Parses the `ytInitialData` object embedded in a watch page.
+pub fn extract_initial_data(webpage: &str) -> Result<Value, YoutubeError> {
+    helper::extract_json_variable(webpage, "ytInitialData").ok_or(YoutubeError::InitialDataMissing)
+}
+
+/// Returns the byte length of the JSON object that `text` starts with, tracking string escapes.
+pub fn find_json_end(text: &str) -> Option<usize> {
+    helper::find_json_end(text)
+}
+
+/// Reads chapters from the `macroMarkersListRenderer` engagement panel of `ytInitialData`. Their end times are left at 0.
+pub fn get_panel_chapters(extractor: &YoutubeExtractor, initial_data: &Value) -> Vec<Chapter> {
+    extractor.get_panel_chapters(initial_data)
+}
+
+/// Turns the `commandRuns` of a video's `attributedDescription` into typed links.
+pub fn get_description_links(attributed_description: &Value, video_id: &str) -> Vec<DescriptionLink> {
+    description_extract::get_description_links(attributed_description, video_id)
+}
+
+/// Parses view/like counts such as `799,831` or `10K`.
+pub fn parse_count(extractor: &YoutubeExtractor, text: &str) -> Option<u64> {
+    extractor.parse_count(text)
+}
+
+/// Resolves a relative comment time such as `21 hours ago` against `now`.
+pub fn parse_published_time(extractor: &YoutubeExtractor, text: &str, now: DateTime<Utc>) -> Option<EstimatedTime> {
+    extractor.parse_published_time(text, now)
+}
+
+/// Resolves a video `dateText` such as `Aug 23, 2025` or `Streamed live 3 hours ago` against `now`.
+pub fn parse_upload_date(extractor: &YoutubeExtractor, text: &str, now: DateTime<Utc>) -> Option<EstimatedTime> {
+    extractor.parse_upload_date(text, now)
+}
+
+/// Tells whether a response is a captcha, sign-in wall or other bot check rather than content.
+pub fn bot_check(response: &HttpResponse) -> Option<BotCheckKind> {
+    bot_check::bot_check(response)
+}
+
+/// Finds the token for the next page of top-level comments in a `youtubei/v1/next` response.
+///
+/// `request_count` is 1 for the first comments response, whose layout differs from later pages.
+pub async fn get_next_continuation_token(extractor: &YoutubeExtractor, data: &Value, request_count: usize) -> Option<String> {
+    extractor.get_next_continuation_token(data, &request_count).await
+}
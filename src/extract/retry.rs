@@ -0,0 +1,156 @@
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use tracing::warn;
+
+use super::YoutubeExtractor;
+use super::bot_check::bot_check;
+use super::error_msgs::YoutubeError;
+use super::transport::HttpResponse;
+
+/// How failed requests are retried.
+///
+/// Throttling (HTTP 429), server errors (5xx) and dropped connections are retried with
+/// exponential backoff; everything else fails straight away. Bot checks are retried only
+/// when a [`ProxyPool`](super::ProxyPool) can send the retry from another IP.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts per request, the first one included. 1 disables retrying.
+    pub max_attempts: u32,
+    /// Wait before the second attempt, doubled for every attempt after it.
+    pub initial_backoff: Duration,
+    /// Upper bound for a single wait, including one asked for by `Retry-After`.
+    pub max_backoff: Duration,
+    /// Randomises each wait between half and all of the backoff, so concurrent crawls don't retry in lockstep.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Fails on the first error.
+    pub fn none() -> Self {
+        Self { max_attempts: 1, ..Self::default() }
+    }
+
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Whether a request that failed with `error` may succeed when sent again.
+    pub fn is_retryable(&self, error: &YoutubeError) -> bool {
+        match error {
+            YoutubeError::RateLimited { .. } => true,
+            YoutubeError::Http { status } => *status >= 500,
+            YoutubeError::Network(e) => e.is_connect() || e.is_timeout() || e.is_request() || e.is_body(),
+            _ => false,
+        }
+    }
+
+    /// The wait after the `attempt`th failed attempt, counting from 1.
+    pub fn backoff(&self, attempt: u32, error: &YoutubeError) -> Duration {
+        if let YoutubeError::RateLimited { retry_after: Some(retry_after), .. } = error {
+            return (*retry_after).min(self.max_backoff);
+        }
+
+        let exponential = self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+        if !self.jitter {
+            return exponential;
+        }
+        let fraction = random_u64() as f64 / u64::MAX as f64;
+        exponential.mul_f64(0.5 + fraction / 2.0)
+    }
+}
+
+/// A random number without pulling in `rand`; std seeds every `RandomState` differently.
+fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+impl YoutubeExtractor {
+
+    /// Bot checks stick to the IP they were served to, so they are only worth retrying through another proxy.
+    fn should_retry(&self, policy: &RetryPolicy, error: &YoutubeError) -> bool {
+        match error {
+            YoutubeError::BotCheck { .. } => self.proxy_pool().is_some_and(|pool| pool.len() > 1),
+            _ => policy.is_retryable(error),
+        }
+    }
+
+    /// Turns throttling, bot checks and other non-success responses into errors.
+    ///
+    /// A 429 stays [`YoutubeError::RateLimited`] whatever its body, so it is retried and its
+    /// `Retry-After` honoured; a bot check page served with it only labels the error.
+    fn classify(&self, response: HttpResponse) -> Result<HttpResponse, YoutubeError> {
+        if let Err(YoutubeError::RateLimited { retry_after, .. }) = response.error_for_status() {
+            return Err(YoutubeError::RateLimited { retry_after, bot_check: bot_check(&response) });
+        }
+        if let Some(kind) = bot_check(&response) {
+            return Err(YoutubeError::BotCheck { kind, url: response.url });
+        }
+        response.error_for_status()?;
+        Ok(response)
+    }
+
+    /// Sends a request through the rate limiter, retrying it according to the configured [`RetryPolicy`].
+    ///
+    /// `send` is called once per attempt so that every attempt picks up the latest cookies.
+    /// Non-success statuses are turned into errors before deciding whether to retry.
+    pub(crate) async fn send_with_retry<F, Fut>(&self, url: &str, mut send: F) -> Result<HttpResponse, YoutubeError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<HttpResponse, YoutubeError>>,
+    {
+        let policy = self.config().retry;
+        let mut attempt = 1;
+
+        loop {
+            self.rate_limiter().acquire().await;
+            let result = send().await.and_then(|response| self.classify(response));
+
+            let error = match result {
+                Ok(response) => return Ok(response),
+                Err(e) if attempt >= policy.max_attempts.max(1) || !self.should_retry(&policy, &e) => return Err(e),
+                Err(e) => e,
+            };
+
+            let backoff = policy.backoff(attempt, &error);
+            if let YoutubeError::RateLimited { retry_after: Some(_), .. } = error {
+                // Hold back every extractor sharing the limiter, not just this request.
+                self.rate_limiter().pause_for(backoff).await;
+            }
+            warn!(error = %error, url, attempt, ?backoff, "Request failed, retrying");
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::config::ExtractorConfig;
+use super::error_msgs::YoutubeError;
+
+/// A response as seen by the extractor, independent of the HTTP client that produced it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// Final URL after redirects.
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Case-insensitive header lookup.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Maps a non-success status to the matching error.
+    pub fn error_for_status(&self) -> Result<(), YoutubeError> {
+        if self.is_success() {
+            return Ok(());
+        }
+        if self.status == 429 {
+            let retry_after = self.header("Retry-After").and_then(parse_retry_after);
+            return Err(YoutubeError::RateLimited { retry_after, bot_check: None });
+        }
+        Err(YoutubeError::Http { status: self.status })
+    }
+}
+
+/// `Retry-After` is either a number of seconds or an HTTP date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((at - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// The network layer used by [`YoutubeExtractor`](super::YoutubeExtractor).
+///
+/// Implement this to mock YouTube in tests or to route requests through custom networking.
+/// The extractor passes every header it wants sent, including `User-Agent`.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Fetches an HTML page such as `/watch?v=…`.
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError>;
+
+    /// POSTs a JSON body to an innertube endpoint such as `youtubei/v1/next`.
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError>;
+}
+
+/// Default [`Transport`] backed by a single pooled `reqwest::Client`.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Builds a client using the timeouts from `config`.
+    ///
+    /// Fails instead of falling back to a default client, which would silently drop the timeouts.
+    pub fn new(config: &ExtractorConfig) -> Result<Self, YoutubeError> {
+        Ok(Self::from_builder(config, reqwest::Client::builder())?)
+    }
+
+    /// Like [`new`](Self::new), with every request going through `proxy`.
+    pub fn with_proxy(config: &ExtractorConfig, proxy: reqwest::Proxy) -> Result<Self, YoutubeError> {
+        Ok(Self::from_builder(config, reqwest::Client::builder().proxy(proxy))?)
+    }
+
+    fn from_builder(config: &ExtractorConfig, mut builder: reqwest::ClientBuilder) -> Result<Self, reqwest::Error> {
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        Ok(Self { client: builder.build()? })
+    }
+
+    /// Wraps an existing client, e.g. one configured for a corporate proxy.
+    pub fn from_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        let request = headers
+            .iter()
+            .fold(request, |request, (key, value)| request.header(key, value));
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let url = response.url().to_string();
+        let headers = response.headers()
+            .iter()
+            .filter_map(|(key, value)| Some((key.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response.text().await?;
+
+        Ok(HttpResponse { status, url, headers, body })
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        self.send(self.client.get(url), headers).await
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        self.send(self.client.post(url).json(body), headers).await
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+use std::str::FromStr;
+
+use percent_encoding::percent_decode_str;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use super::error_msgs::YoutubeError;
+
+/// An 11 character YouTube video ID drawn from `[A-Za-z0-9_-]`.
+///
+/// Parsing accepts either a bare ID or any URL that [`YoutubeUrl`] recognises as a video.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct VideoId(String);
+
+impl VideoId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether `id` has the shape of a video ID. Says nothing about whether the video exists.
+    pub fn is_valid(id: &str) -> bool {
+        id.len() == 11 && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    }
+
+    pub fn watch_url(&self) -> String {
+        format!("https://www.youtube.com/watch?v={}", self.0)
+    }
+}
+
+impl FromStr for VideoId {
+    type Err = YoutubeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if Self::is_valid(input) {
+            return Ok(Self(input.to_string()));
+        }
+        match input.parse::<YoutubeUrl>() {
+            Ok(YoutubeUrl::Video { id, .. }) => Ok(id),
+            _ => Err(YoutubeError::InvalidVideoId(input.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for VideoId {
+    type Error = YoutubeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<VideoId> for String {
+    fn from(id: VideoId) -> Self {
+        id.0
+    }
+}
+
+impl AsRef<str> for VideoId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for VideoId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The different ways a channel is addressed in URLs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChannelRef {
+    /// `/@handle`, stored without the `@`.
+    Handle(String),
+    /// `/channel/UC…`
+    Id(String),
+    /// Legacy `/c/name` custom URL.
+    Custom(String),
+    /// Legacy `/user/name` URL.
+    User(String),
+}
+
+/// A classified YouTube URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum YoutubeUrl {
+    Video {
+        id: VideoId,
+        /// From `t=` / `start=` in the query or fragment.
+        start_seconds: Option<u64>,
+        /// Set when the video was opened from a playlist (`list=`).
+        playlist_id: Option<String>,
+    },
+    Channel(ChannelRef),
+    Playlist { id: String },
+    Search { query: String },
+}
+
+impl FromStr for YoutubeUrl {
+    type Err = YoutubeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim();
+        let with_scheme = if trimmed.contains("://") {
+            trimmed.to_string()
+        } else {
+            format!("https://{trimmed}")
+        };
+        let url = Url::parse(&with_scheme)
+            .map_err(|_| YoutubeError::UnrecognizedUrl(input.to_string()))?;
+
+        parse_url(&url).ok_or_else(|| YoutubeError::UnrecognizedUrl(input.to_string()))
+    }
+}
+
+fn parse_url(url: &Url) -> Option<YoutubeUrl> {
+    let host = url.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+
+    let segments: Vec<&str> = url.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    if host == "youtu.be" {
+        return video(segments.first()?, url);
+    }
+
+    let is_youtube = host == "youtube.com"
+        || host.ends_with(".youtube.com")
+        || host == "youtube-nocookie.com"
+        || host.ends_with(".youtube-nocookie.com");
+    if !is_youtube {
+        return None;
+    }
+
+    match segments.as_slice() {
+        ["watch", ..] | [] => match query_value(url, "v") {
+            Some(id) => video(&id, url),
+            None => query_value(url, "list").map(|id| YoutubeUrl::Playlist { id }),
+        },
+        ["shorts" | "live" | "embed" | "v" | "e", id, ..] => video(id, url),
+        ["attribution_link", ..] => {
+            let target = query_value(url, "u")?;
+            let target = url.join(&target).ok()?;
+            parse_url(&target)
+        }
+        ["playlist", ..] => query_value(url, "list").map(|id| YoutubeUrl::Playlist { id }),
+        ["results", ..] => query_value(url, "search_query")
+            .or_else(|| query_value(url, "q"))
+            .map(|query| YoutubeUrl::Search { query }),
+        ["channel", id, ..] => Some(YoutubeUrl::Channel(ChannelRef::Id(id.to_string()))),
+        ["c", name, ..] => Some(YoutubeUrl::Channel(ChannelRef::Custom(decode(name)))),
+        ["user", name, ..] => Some(YoutubeUrl::Channel(ChannelRef::User(decode(name)))),
+        [handle, ..] if handle.starts_with('@') && handle.len() > 1 => {
+            Some(YoutubeUrl::Channel(ChannelRef::Handle(decode(&handle[1..]))))
+        }
+        _ => None,
+    }
+}
+
+fn video(id: &str, url: &Url) -> Option<YoutubeUrl> {
+    if !VideoId::is_valid(id) {
+        return None;
+    }
+    let start_seconds = query_value(url, "t")
+        .or_else(|| query_value(url, "start"))
+        .or_else(|| fragment_value(url, "t"))
+        .and_then(|t| parse_timestamp(&t));
+
+    Some(YoutubeUrl::Video {
+        id: VideoId(id.to_string()),
+        start_seconds,
+        playlist_id: query_value(url, "list"),
+    })
+}
+
+fn query_value(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+        .filter(|v| !v.is_empty())
+}
+
+fn fragment_value(url: &Url, key: &str) -> Option<String> {
+    url.fragment()?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v.to_string())
+}
+
+fn decode(segment: &str) -> String {
+    percent_decode_str(segment).decode_utf8_lossy().into_owned()
+}
+
+/// Parses `90`, `90s`, `1m30s` or `1h2m3s` into seconds.
+fn parse_timestamp(text: &str) -> Option<u64> {
+    if let Ok(seconds) = text.parse() {
+        return Some(seconds);
+    }
+
+    let mut total: u64 = 0;
+    let mut number = String::new();
+    for ch in text.chars() {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+        let value: u64 = number.parse().ok()?;
+        number.clear();
+        let unit = match ch {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        // Absurdly large timestamps are dropped rather than overflowing.
+        total = value.checked_mul(unit).and_then(|seconds| total.checked_add(seconds))?;
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(total)
+}
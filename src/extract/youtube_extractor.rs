@@ -1,42 +1,242 @@
+use std::sync::{Arc, LazyLock};
+
 use async_std::fs;
+use serde::Serialize;
 use serde_json::{json, Value};
 use regex::Regex;
+use reqwest::Url;
 use crate::models::VideoInfo;
 use crate::models::Comment;
-pub struct YoutubeExtractor;
-use tracing::{info, error, debug, instrument};
-
+use super::clock::{Clock, SystemClock};
+use super::config::{ExtractorBuilder, ExtractorConfig};
+use super::cookies::CookieJar;
+use super::proxy::ProxyPool;
+use super::error_msgs::YoutubeError;
+use super::helper;
+use super::locale::{self, DisplayCount, Locale};
+use super::rate_limit::RateLimiter;
+use super::transport::{HttpResponse, ReqwestTransport, Transport};
+use super::url::VideoId;
+use tracing::{info, error, debug, warn, instrument};
+
+static YTCFG_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"ytcfg\.set\s*\(\s*(\{.+?\})\s*\)").expect("valid ytcfg regex")
+});
+
+static WINDOW_YTCFG_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"window\["ytcfg"\].*?ytcfg\.set\s*\(\s*(\{.+?\})\s*\)"#).expect("valid window ytcfg regex")
+});
+
+const YOUTUBE_ORIGIN: &str = "https://www.youtube.com";
+
+const TITLE_PATH: &[&str] = &[
+    "contents", "twoColumnWatchNextResults", "results",
+    "results", "contents", "0", "videoPrimaryInfoRenderer", "title"
+];
+
+#[derive(Clone)]
+pub struct YoutubeExtractor {
+    config: ExtractorConfig,
+    transport: Arc<dyn Transport>,
+    rate_limiter: Arc<RateLimiter>,
+    clock: Arc<dyn Clock>,
+    cookie_jar: Arc<CookieJar>,
+    proxy_pool: Option<Arc<ProxyPool>>,
+    /// Proxy every request of this copy goes through, set by [`for_video`](Self::for_video).
+    pinned_proxy: Option<usize>,
+}
+
+impl Default for YoutubeExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl YoutubeExtractor{
 
     pub fn new() -> Self {
-        Self
+        Self::with_config(ExtractorConfig::default())
+    }
+
+    /// # Panics
+    ///
+    /// If the HTTP client cannot be built; [`ExtractorBuilder::try_build`](super::ExtractorBuilder::try_build) reports that as an error instead.
+    pub fn with_config(config: ExtractorConfig) -> Self {
+        let transport = Arc::new(ReqwestTransport::new(&config).expect("HTTP client with the configured timeouts"));
+        Self::with_transport(config, transport)
+    }
+
+    pub fn with_transport(config: ExtractorConfig, transport: Arc<dyn Transport>) -> Self {
+        let rate_limiter = Arc::new(RateLimiter::with_burst(config.request_delay, config.request_burst));
+        Self {
+            config,
+            transport,
+            rate_limiter,
+            clock: Arc::new(SystemClock),
+            cookie_jar: Arc::new(CookieJar::new()),
+            proxy_pool: None,
+            pinned_proxy: None,
+        }
+    }
+
+    pub(crate) fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    pub(crate) fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub(crate) fn with_cookie_jar(mut self, cookie_jar: Arc<CookieJar>) -> Self {
+        self.cookie_jar = cookie_jar;
+        self
+    }
+
+    pub(crate) fn with_proxy_pool(mut self, proxy_pool: Arc<ProxyPool>) -> Self {
+        self.proxy_pool = Some(proxy_pool);
+        self
+    }
+
+    pub(crate) fn with_pinned_proxy(mut self, proxy: Option<usize>) -> Self {
+        self.pinned_proxy = proxy;
+        self
+    }
+
+    pub fn builder() -> ExtractorBuilder {
+        ExtractorBuilder::default()
+    }
+
+    pub fn config(&self) -> &ExtractorConfig {
+        &self.config
+    }
+
+    pub fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
+    }
+
+    pub fn rate_limiter(&self) -> &Arc<RateLimiter> {
+        &self.rate_limiter
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        &self.cookie_jar
+    }
+
+    pub fn proxy_pool(&self) -> Option<&Arc<ProxyPool>> {
+        self.proxy_pool.as_ref()
+    }
+
+    pub(crate) fn pinned_proxy(&self) -> Option<usize> {
+        self.pinned_proxy
+    }
+
+    /// Headers sent with every request to `url`, regardless of transport.
+    pub(crate) fn request_headers(&self, url: &str) -> Vec<(String, String)> {
+        vec![
+            ("User-Agent".to_string(), self.config.user_agent.clone()),
+            ("Accept-Language".to_string(), self.config.accept_language()),
+            ("Cookie".to_string(), self.cookie_header(url)),
+        ]
+    }
+
+    /// Cookies from the config, overridden by same-named ones from the jar.
+    fn cookie_header(&self, url: &str) -> String {
+        let jar_cookies = self.cookie_jar.cookies_for(url, self.clock.now());
+        let mut cookies = self.config.cookies();
+        cookies.retain(|(name, _)| !jar_cookies.iter().any(|(jar_name, _)| jar_name == name));
+        cookies.into_iter()
+            .chain(jar_cookies)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// The `SAPISIDHASH` headers innertube expects for logged-in requests, if the jar holds a `SAPISID`.
+    pub(crate) fn authorization_headers(&self) -> Vec<(String, String)> {
+        let Some(sapisid) = self.cookie_jar.get("SAPISID").or_else(|| self.cookie_jar.get("__Secure-3PAPISID")) else {
+            return Vec::new();
+        };
+        let timestamp = self.clock.now().timestamp();
+        let hash = sha1_smol::Sha1::from(format!("{timestamp} {sapisid} {YOUTUBE_ORIGIN}")).digest().to_string();
+
+        vec![
+            ("Authorization".to_string(), format!("SAPISIDHASH {timestamp}_{hash}")),
+            ("X-Origin".to_string(), YOUTUBE_ORIGIN.to_string()),
+            ("X-Goog-AuthUser".to_string(), "0".to_string()),
+        ]
+    }
+
+    /// GETs a page with the standard headers, retrying per the configured policy.
+    pub(crate) async fn get_page(&self, url: &str) -> Result<HttpResponse, YoutubeError> {
+        self.send_with_retry(url, || async move {
+            let (transport, proxy) = self.request_transport();
+            let response = transport.get_page(url, &self.request_headers(url)).await;
+            self.report_proxy(proxy, &response);
+            let response = response?;
+            self.store_cookies(url, &response);
+            Ok(response)
+        }).await
+    }
+
+    /// Keeps the session cookies a response sets for the requests that follow.
+    pub(crate) fn store_cookies(&self, url: &str, response: &HttpResponse) {
+        self.cookie_jar.store_response(url, &response.headers, self.clock.now());
+    }
+
+    /// The innertube `context.client` sent with every `youtubei/v1` request.
+    pub(crate) fn innertube_client(&self) -> Value {
+        json!({
+            "clientName": self.config.client_name,
+            "clientVersion": self.config.client_version,
+            "hl": self.config.language,
+            "gl": self.config.region,
+        })
     }
 
     #[instrument(skip(self))]
-    pub async fn extract(&self, video: &str) -> Result<(VideoInfo, Vec<Comment>), Box<dyn std::error::Error>>  {
-        let create_json_files = false;
-        let video_id = self.extract_video_id(video).unwrap_or_default();
+    pub async fn extract(&self, video: &str) -> Result<(VideoInfo, Vec<Comment>), YoutubeError>  {
+        self.for_video().extract_video(video).await
+    }
+
+    async fn extract_video(&self, video: &str) -> Result<(VideoInfo, Vec<Comment>), YoutubeError> {
+        let video_id: VideoId = video.parse()?;
 
         info!("Beginning extraction for video ID: {}", video_id);
 
-        let webpage = self.get_json(&video_id).await.unwrap_or_default();
-        let initial_data = self.extract_initial_data(&webpage).unwrap_or_default();
+        let webpage = self.get_json(&video_id).await?;
+        let fetched_at = self.clock.now();
+        let player_response = self.extract_player_response(&webpage);
+        if let Some(player_response) = &player_response {
+            self.dump_json("player_response.json", player_response).await;
+        }
+        self.check_playability(player_response.as_ref())?;
+        let initial_data = self.extract_initial_data(&webpage)?;
+
+        self.dump_json("output.json", &initial_data).await;
 
-        if create_json_files {
-            let json_str = serde_json::to_string_pretty(&initial_data).unwrap_or_default();
-            fs::write("output.json", json_str).await.unwrap();
+        let mut video_info = self.extract_video_info(&initial_data, video_id.as_str());
+        video_info.fetched_at = fetched_at;
+        video_info.uploaded_at = self.parse_upload_date(&video_info.upload_date, fetched_at);
+        if let Some(player_response) = &player_response {
+            self.apply_player_response(player_response, &mut video_info);
         }
+        self.apply_description(&initial_data, video_id.as_str(), &mut video_info);
 
-        let mut video_info = self.extract_video_info(&initial_data, &video_id);
+        if video_info.title.is_empty() {
+            return Err(YoutubeError::SchemaChanged { path: TITLE_PATH.join(".") });
+        }
 
         if video_info.yt_id.is_empty() {
-            video_info.yt_id = video_id.clone();
+            video_info.yt_id = video_id.to_string();
         }
 
-        if create_json_files {
-            self.save_video_info_to_json(&video_info, "video_info.json").await?;
-        }
+        self.dump_json("video_info.json", &video_info).await;
 
         debug!(
         title = %video_info.title,
@@ -52,11 +252,9 @@ impl YoutubeExtractor{
 
         info!("Extracted video metadata...");
 
-        let ytcfg = self.extract_ytcfg(&webpage, create_json_files).await?;
-
-        let comments = self.get_comments(&initial_data, &ytcfg, &video_id, Some(25), create_json_files).await;
-
+        let ytcfg = self.extract_ytcfg(&webpage).await?;
 
+        let comments = self.get_comments(&initial_data, &ytcfg, video_id.as_str()).await;
 
         match comments {
             Ok(comments_data) => {
@@ -65,44 +263,32 @@ impl YoutubeExtractor{
                 Ok((video_info, comments_data))
 
             }
+            Err(YoutubeError::CommentsDisabled) => {
+                info!(video_id = &video_info.yt_id, "Comments are turned off, skipping comment extraction");
+                Ok((video_info, Vec::new()))
+            }
             Err(e) => {
                 error!(error = %e, video_id = &video_info.yt_id, "Failed to extract comments");
-                Ok((video_info, Vec::new()))
+                Err(e)
             }
         }
     }
 
-    pub async fn extract_ytcfg(&self, webpage: &str, create_json_files: bool) -> Result<Value, Box<dyn std::error::Error>> {
+    pub(crate) async fn extract_ytcfg(&self, webpage: &str) -> Result<Value, YoutubeError> {
         // Pattern 1: ytcfg.set({...})
-        let pattern1 = Regex::new(r"ytcfg\.set\s*\(\s*(\{.+?\})\s*\)")?;
-        if let Some(captures) = pattern1.captures(webpage) {
-            if let Some(json_str) = captures.get(1) {
-                match serde_json::from_str::<Value>(json_str.as_str()) {
-                    Ok(data) => {
-                        if create_json_files{
-                            let json_str = serde_json::to_string_pretty(&data).unwrap_or_default();
-                            fs::write("ytcfg_p1.json", &json_str).await.unwrap();
-                        }
-                        return Ok(data)
-                    },
-                    Err(_) => {}
-                }
-            }
+        if let Some(captures) = YTCFG_PATTERN.captures(webpage)
+            && let Some(json_str) = captures.get(1)
+            && let Ok(data) = serde_json::from_str::<Value>(json_str.as_str()) {
+            self.dump_json("ytcfg_p1.json", &data).await;
+            return Ok(data)
         }
 
         // Pattern 2: window["ytcfg"] = ... ytcfg.set({...})
-        let pattern2 = Regex::new(r#"window\["ytcfg"\].*?ytcfg\.set\s*\(\s*(\{.+?\})\s*\)"#)?;
-        if let Some(captures) = pattern2.captures(webpage) {
-            if let Some(json_str) = captures.get(1) {
-                match serde_json::from_str::<Value>(json_str.as_str()) {
-                    Ok(data) => {
-                        let json_str = serde_json::to_string_pretty(&data).unwrap_or_default();
-                        fs::write("ytcfg_p2.json", &json_str).await.unwrap();
-                        return Ok(data)
-                    },
-                    Err(_) => {}
-                }
-            }
+        if let Some(captures) = WINDOW_YTCFG_PATTERN.captures(webpage)
+            && let Some(json_str) = captures.get(1)
+            && let Ok(data) = serde_json::from_str::<Value>(json_str.as_str()) {
+            self.dump_json("ytcfg_p2.json", &data).await;
+            return Ok(data)
         }
         
         debug!("🥎🥎 Using hardcoded keys. 🥎🥎");
@@ -118,129 +304,72 @@ impl YoutubeExtractor{
     }))
     }
 
-    fn extract_initial_data(&self, webpage: &str) -> Result<Value, Box<dyn std::error::Error>> {
-        let patterns = [
-            "window[\"ytInitialData\"] = ",
-            "window['ytInitialData'] = ",
-            "ytInitialData = ",
-            "var ytInitialData = "
-        ];
-
-        for pattern in &patterns {
-            if let Some(start) = webpage.find(pattern) {
-                let json_start = start + pattern.len();
-
-                if let Some(end) = self.find_json_end(&webpage[json_start..]) {
-                    let json_str = &webpage[json_start..json_start + end];
-
-                    match serde_json::from_str::<Value>(json_str) {
-                        Ok(data) => return Ok(data),
-                        Err(_) => continue,
-                    }
-                }
-            }
-        }
-        Err("Could not extract ytInitialData".into())
+    pub(crate) fn extract_initial_data(&self, webpage: &str) -> Result<Value, YoutubeError> {
+        self.extract_json_variable(webpage, "ytInitialData")
+            .ok_or(YoutubeError::InitialDataMissing)
     }
 
-    fn find_json_end(&self, text: &str) -> Option<usize> {
-        let mut depth = 0;
-        let mut in_string = false;
-        let mut escaped = false;
-
-        for (i, ch) in text.char_indices() {
-            if escaped {
-                escaped = false;
-                continue;
-            }
+    /// Finds `name = {...}` (or `window["name"] = {...}`) in the page and parses the object.
+    pub(crate) fn extract_json_variable(&self, webpage: &str, name: &str) -> Option<Value> {
+        helper::extract_json_variable(webpage, name)
+    }
 
-            match ch {
-                '\\' if in_string => escaped = true,
-                '"' => in_string = !in_string,
-                '{' if !in_string => depth += 1,
-                '}' if !in_string => {
-                    depth -= 1;
-                    if depth == 0 {
-                        return Some(i + 1);
-                    }
-                },
-                _ => {}
-            }
-        }
-        None
-    }
-
-    async fn get_json(&self, video_id: &String) -> Result<String, Box<dyn std::error::Error>>{
-        let url = format!("https://www.youtube.com/watch?v={}&bpctr=9999999999&has_verified=1", video_id);
-        let html = reqwest::get(&url).await;
-        let webpage = html.unwrap().text().await?;
-        Ok(webpage)
-    }
-
-    fn parse_count(&self, text: &str) -> Option<u64> {
-        let text = text.to_lowercase().replace(&[',', ' '][..], "");
-
-        if text.ends_with('k') {
-            text.trim_end_matches('k').parse::<f64>().ok()
-                .map(|n| (n * 1000.0) as u64)
-        } else if text.ends_with('m') {
-            text.trim_end_matches('m').parse::<f64>().ok()
-                .map(|n| (n * 1000000.0) as u64)
-        } else if text.ends_with('b') {
-            text.trim_end_matches('b').parse::<f64>().ok()
-                .map(|n| (n * 1000000000.0) as u64)
-        } else {
-            text.parse().ok()
+    pub(crate) async fn get_json(&self, video_id: &VideoId) -> Result<String, YoutubeError>{
+        let url = Url::parse_with_params(&video_id.watch_url(), &[
+            ("hl", self.config.language.as_str()),
+            ("gl", self.config.region.as_str()),
+            ("bpctr", "9999999999"),
+            ("has_verified", "1"),
+        ]).expect("watch URLs of validated video IDs parse");
+        let response = self.get_page(url.as_str()).await?;
+
+        if self.is_consent_page(&response) {
+            warn!(bypass_consent = self.config.bypass_consent, "Got the cookie consent page instead of the watch page");
+            return Err(YoutubeError::ConsentRequired);
         }
+        Ok(response.body)
     }
 
-    pub fn get_text_from_path(&self, data: &Value, path: &[&str]) -> Option<String> {
-        let mut current = data;
+    /// The EU consent interstitial, either redirected to or served in place of the watch page.
+    pub(crate) fn is_consent_page(&self, response: &HttpResponse) -> bool {
+        let on_consent_host = Url::parse(&response.url).ok()
+            .and_then(|url| url.host_str().map(|host| host.starts_with("consent.")))
+            .unwrap_or(false);
+        on_consent_host
+            || response.body.contains("action=\"https://consent.youtube.com")
+            || response.body.contains("action=\"https://consent.google.com")
+    }
 
-        for key in path {
-            current = if key.chars().all(char::is_numeric) {
-                let index: usize = key.parse().ok()?;
-                current.as_array()?.get(index)?
-            } else {
-                current.get(key)?
-            }
-        }
+    pub(crate) fn parse_count(&self, text: &str) -> Option<u64> {
+        self.parse_display_count(text).map(|count| count.value)
+    }
 
-        if let Some(s) = current.as_str() {
-            return Some(s.to_string());
-        }
+    /// Like [`parse_count`](Self::parse_count), but also tells whether the number was abbreviated.
+    pub(crate) fn parse_display_count(&self, text: &str) -> Option<DisplayCount> {
+        self.locale().parse_count(text)
+    }
 
-        if let Some(b) = current.as_bool() {
-            return Some(b.to_string());
-        }
+    /// Number and date wording for the configured `hl` language.
+    pub(crate) fn locale(&self) -> &'static Locale {
+        locale::for_language(&self.config.language)
+    }
 
-        if let Some(runs) = current.get("runs").and_then(|r| r.as_array()) {
-            let text: String = runs
-                .iter()
-                .filter_map(|run| run.get("text")?.as_str())
-                .collect();
-            if !text.is_empty() {
-                return Some(text);
-            }
-        }
-        None
+    pub(crate) fn get_text_from_path(&self, data: &Value, path: &[&str]) -> Option<String> {
+        helper::get_text_from_path(data, path)
     }
 
     fn get_views(&self, data: &Value) -> u64{
-        let views_string = self.get_text_from_path(&data, &[
+        let views_string = self.get_text_from_path(data, &[
             "playerOverlays", "playerOverlayRenderer", "videoDetails", "playerOverlayVideoDetailsRenderer",
             "subtitle", "runs", "2", "text"
         ]).unwrap_or_default();
 
-        views_string
-            .split_whitespace()
-            .next()
-            .and_then(|split| self.parse_count(split))
-            .unwrap_or(0)
+        // "799,831 views", "799.831 Aufrufe", "79万 回視聴"
+        self.parse_count(&views_string).unwrap_or(0)
     }
 
     fn get_comment_count(&self, data: &Value) -> u64{
-        let comment_count_string = self.get_text_from_path(&data, &[
+        let comment_count_string = self.get_text_from_path(data, &[
             "engagementPanels", "0", "engagementPanelSectionListRenderer", "header", "engagementPanelTitleHeaderRenderer",
             "contextualInfo", "runs", "0", "text"
         ]).unwrap_or_default();
@@ -249,7 +378,7 @@ impl YoutubeExtractor{
     }
 
     fn get_likes(&self, data: &Value) -> u64{
-        let like_count_string = self.get_text_from_path(&data, &[
+        let like_count_string = self.get_text_from_path(data, &[
             "contents", "twoColumnWatchNextResults", "results", "results", "contents",
             "0", "videoPrimaryInfoRenderer", "videoActions", "menuRenderer",
             "topLevelButtons", "0", "segmentedLikeDislikeButtonViewModel",
@@ -258,15 +387,12 @@ impl YoutubeExtractor{
             "accessibilityText"
         ]).unwrap_or_default();
 
-        like_count_string
-            .split_whitespace()
-            .nth(5)
-            .and_then(|num| self.parse_count(num))
-            .unwrap_or(0)
+        // "like this video along with 10,464 other people"; the only number in any language
+        self.parse_count(&like_count_string).unwrap_or(0)
     }
 
     fn get_channel_id(&self, data: &Value) -> String {
-        self.get_text_from_path(&data, &[
+        self.get_text_from_path(data, &[
             "contents", "twoColumnWatchNextResults", "results",
             "results", "contents", "1", "videoSecondaryInfoRenderer",
             "subscribeButton", "subscribeButtonRenderer", "channelId"
@@ -278,7 +404,7 @@ impl YoutubeExtractor{
     }
     
     fn get_upload_date(&self, data: &Value) -> String {
-        self.get_text_from_path(&data, &[
+        self.get_text_from_path(data, &[
             "contents", "twoColumnWatchNextResults", "results",
             "results", "contents", "0", "videoPrimaryInfoRenderer",
             "dateText", "simpleText"
@@ -286,7 +412,7 @@ impl YoutubeExtractor{
     }
     
     fn get_channel_thumbnail(&self, data: &Value) -> String {
-        self.get_text_from_path(&data, &[
+        self.get_text_from_path(data, &[
             "contents", "twoColumnWatchNextResults", "results",
             "results", "contents", "1", "videoSecondaryInfoRenderer",
             "owner", "videoOwnerRenderer", "thumbnail", "thumbnails",
@@ -297,10 +423,7 @@ impl YoutubeExtractor{
 
     fn extract_video_info(&self, initial_data: &Value, video_id: &str) -> VideoInfo {
         VideoInfo {
-            title: self.get_text_from_path(initial_data, &[
-                "contents", "twoColumnWatchNextResults", "results",
-                "results", "contents", "0", "videoPrimaryInfoRenderer", "title"
-            ]).unwrap_or_default(),
+            title: self.get_text_from_path(initial_data, TITLE_PATH).unwrap_or_default(),
 
             channel: self.get_text_from_path(initial_data, &[
                 "contents", "twoColumnWatchNextResults", "results",
@@ -326,30 +449,35 @@ impl YoutubeExtractor{
 
             like_count: self.get_likes(initial_data),
             
-            video_thumbnail: self.get_video_thumbnail(&video_id),
+            video_thumbnail: self.get_video_thumbnail(video_id),
             
             upload_date: self.get_upload_date(initial_data),
             
-            channel_thumbnail: self.get_channel_thumbnail(initial_data)
-        }
-    }
+            channel_thumbnail: self.get_channel_thumbnail(initial_data),
 
-    fn extract_video_id(&self, input: &str) -> Option<String> {
-        if input.len() == 11 && !input.contains('/') {
-            Some(input.to_string())
-        } else {
-            input.split("v=")
-                .nth(1)?
-                .split('&')
-                .next()
-                .map(|s| s.to_string())
+            ..VideoInfo::default()
         }
     }
 
-    pub async fn save_video_info_to_json(&self, video_info: &VideoInfo, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn save_video_info_to_json(&self, video_info: &VideoInfo, file_path: &str) -> Result<(), YoutubeError> {
         let json_str = serde_json::to_string_pretty(video_info)?;
         fs::write(file_path, json_str).await?;
         info!("Video info saved to {}", file_path);
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Writes `data` into the configured debug dump directory, if any.
+    pub(crate) async fn dump_json<T: Serialize + ?Sized>(&self, file_name: &str, data: &T) {
+        let Some(dir) = &self.config.debug_dump_dir else {
+            return;
+        };
+        let json_str = serde_json::to_string_pretty(data).unwrap_or_default();
+        if let Err(e) = fs::create_dir_all(dir).await {
+            warn!(error = %e, "Failed to create debug dump directory");
+            return;
+        }
+        if let Err(e) = fs::write(dir.join(file_name), json_str).await {
+            warn!(error = %e, file_name, "Failed to write debug dump");
+        }
+    }
+}
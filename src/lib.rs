@@ -0,0 +1,22 @@
+mod extract;
+mod models;
+
+pub use extract::{CommentOptions, CommentSort, ExtractorBuilder, ExtractorConfig, YoutubeExtractor};
+pub use extract::error_msgs::{BotCheckKind, YoutubeError};
+pub use extract::{HttpResponse, ReqwestTransport, Transport};
+pub use extract::{RateLimiter, RetryPolicy};
+pub use extract::{Clock, FixedClock, SystemClock};
+pub use extract::{Cookie, CookieJar};
+pub use extract::{ProxyPool, ProxyStats, Rotation};
+pub use extract::{RecordingTransport, ReplayTransport};
+pub use extract::cassette;
+pub use extract::{ChannelRef, VideoId, YoutubeUrl};
+pub use extract::{CrawlCheckpoint, CrawlEvent, PendingItem};
+pub use extract::raw;
+pub use models::{Comment, CommentContent, EstimatedTime, MemberBadge, PlayabilityStatus, Thumbnail, TimePrecision, VideoInfo};
+pub use models::{Chapter, DescriptionLink, DescriptionLinkKind};
+pub use models::{CaptionFormat, CaptionKind, CaptionTrack, Transcript, TranscriptSegment};
+
+pub mod prelude {
+    pub use crate::{Comment, CommentOptions, CommentSort, ExtractorConfig, VideoId, VideoInfo, YoutubeError, YoutubeExtractor, YoutubeUrl};
+}
@@ -1,6 +1,4 @@
-mod extract;
-mod models;
-use extract::YoutubeExtractor;
+use yt_scraper::YoutubeExtractor;
 
 #[async_std::main]
 async fn main() {
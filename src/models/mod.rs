@@ -1,5 +1,12 @@
+#[allow(clippy::module_inception)]
 mod models;
 
 pub use models::VideoInfo;
 pub use models::Comment;
-pub use models::CommentContent;
\ No newline at end of file
+pub use models::CommentContent;
+pub use models::MemberBadge;
+pub use models::{EstimatedTime, TimePrecision};
+pub use models::PlayabilityStatus;
+pub use models::Thumbnail;
+pub use models::{Chapter, DescriptionLink, DescriptionLinkKind};
+pub use models::{CaptionFormat, CaptionKind, CaptionTrack, Transcript, TranscriptSegment};
\ No newline at end of file
@@ -1,6 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct VideoInfo {
     pub title: String,
     pub channel: String,
@@ -11,11 +12,79 @@ pub struct VideoInfo {
     pub comment_count: u64,
     pub like_count: u64,
     pub video_thumbnail: String,
+    /// As shown under the video: "Aug 23, 2025", "Premiered …", "Streamed live …".
     pub upload_date: String,
+    /// `upload_date` resolved against `fetched_at`.
+    pub uploaded_at: Option<EstimatedTime>,
+    /// When the watch page was fetched.
+    pub fetched_at: DateTime<Utc>,
     pub channel_thumbnail: String,
+    pub duration_seconds: u64,
+    pub keywords: Vec<String>,
+    pub category: String,
+    pub is_live_content: bool,
+    pub is_private: bool,
+    pub is_unlisted: bool,
+    pub is_family_safe: bool,
+    pub available_countries: Vec<String>,
+    /// ISO 8601, e.g. `2025-08-23T09:12:45-07:00`.
+    pub publish_date_iso: Option<String>,
+    /// ISO 8601, e.g. `2025-08-23T09:12:45-07:00`.
+    pub upload_date_iso: Option<String>,
+    pub thumbnails: Vec<Thumbnail>,
+    pub playability: PlayabilityStatus,
+    pub caption_tracks: Vec<CaptionTrack>,
+    pub chapters: Vec<Chapter>,
+    /// Links, hashtags, @mentions and timestamps marked up in the description.
+    pub description_links: Vec<DescriptionLink>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Thumbnail {
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Chapter {
+    pub title: String,
+    pub start_seconds: u64,
+    /// Start of the next chapter, or the video length for the last one.
+    pub end_seconds: u64,
+    /// Only set for chapters from the chapters panel, not for ones parsed from the description.
+    pub thumbnail: Option<Thumbnail>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DescriptionLinkKind {
+    #[default]
+    Url,
+    Hashtag,
+    Mention,
+    /// A `1:05` style link into the video itself.
+    Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct DescriptionLink {
+    pub kind: DescriptionLinkKind,
+    /// The linked text as shown in the description.
+    pub text: String,
+    /// Absolute target URL, with YouTube's `/redirect` wrapper removed.
+    pub url: String,
+    /// Set for [`DescriptionLinkKind::Timestamp`] links.
+    pub start_seconds: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct PlayabilityStatus {
+    /// `OK`, `LOGIN_REQUIRED`, `UNPLAYABLE`, `ERROR`, `LIVE_STREAM_OFFLINE`, ...
+    pub status: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Comment {
     pub comment_id: String,
     pub channel_id: String,
@@ -24,12 +93,32 @@ pub struct Comment {
     pub user_verified: bool,
     pub thumbnail: String,
     pub content: String,
+    /// As shown by YouTube, e.g. "21 hours ago".
     pub published_time: String,
-    pub like_count: i32,
-    pub reply_count: i32,
+    /// `published_time` resolved against `fetched_at`.
+    pub published_at: Option<EstimatedTime>,
+    /// When the page holding this comment was fetched.
+    pub fetched_at: DateTime<Utc>,
+    pub like_count: u64,
+    /// False when YouTube only showed an abbreviation like "1.2K".
+    pub like_count_exact: bool,
+    pub reply_count: u64,
+    pub reply_count_exact: bool,
     pub comment_level: i32,
     pub reply_to: String,
     pub reply_order: i32,
+    /// The channel's `@handle`, if the payload carries one.
+    pub author_handle: Option<String>,
+    /// The author owns the channel the video was uploaded to.
+    pub is_creator: bool,
+    /// Pinned by the creator. Only top-level comments can be pinned.
+    pub is_pinned: bool,
+    /// The creator gave the comment a heart.
+    pub is_hearted: bool,
+    /// Marked "(edited)", or its translation such as "(bearbeitet)", in the published time.
+    pub is_edited: bool,
+    /// Set when the author is a channel member.
+    pub member_badge: Option<MemberBadge>,
 }
 
 impl Comment {
@@ -47,18 +136,28 @@ impl Comment {
             thumbnail: content.thumbnail,
             content: content.content,
             published_time: content.published_time,
+            published_at: content.published_at,
+            fetched_at: content.fetched_at,
             like_count: content.like_count,
+            like_count_exact: content.like_count_exact,
             reply_count: content.reply_count,
+            reply_count_exact: content.reply_count_exact,
             video_id: content.video_id,
             comment_level,
             reply_to,
             reply_order,
+            author_handle: content.author_handle,
+            is_creator: content.is_creator,
+            is_pinned: content.is_pinned,
+            is_hearted: content.is_hearted,
+            is_edited: content.is_edited,
+            member_badge: content.member_badge,
         }
 
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CommentContent {
     pub comment_id: String,
     pub channel_id: String,
@@ -68,6 +167,147 @@ pub struct CommentContent {
     pub thumbnail: String,
     pub content: String,
     pub published_time: String,
-    pub like_count: i32,
-    pub reply_count: i32,
-}
\ No newline at end of file
+    pub published_at: Option<EstimatedTime>,
+    pub fetched_at: DateTime<Utc>,
+    pub like_count: u64,
+    pub like_count_exact: bool,
+    pub reply_count: u64,
+    pub reply_count_exact: bool,
+    pub author_handle: Option<String>,
+    pub is_creator: bool,
+    /// Always `false` here; pinning is set from the comment thread by the page parser.
+    pub is_pinned: bool,
+    /// Always `false` here; hearts live in a separate toolbar-state mutation.
+    pub is_hearted: bool,
+    pub is_edited: bool,
+    pub member_badge: Option<MemberBadge>,
+}
+
+/// The unit a date was rounded to before YouTube displayed it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum TimePrecision {
+    #[default]
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+/// A UTC time reconstructed from display text such as "21 hours ago" or "Aug 23, 2025".
+///
+/// The real time is within one `precision` unit of `timestamp`: "3 weeks ago" may be anything up to four weeks back.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EstimatedTime {
+    pub timestamp: DateTime<Utc>,
+    pub precision: TimePrecision,
+}
+
+/// A channel membership badge shown next to the author's name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct MemberBadge {
+    /// Accessibility label, e.g. "Member (6 months)".
+    pub label: String,
+    pub thumbnail_url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaptionKind {
+    /// Uploaded by the creator.
+    #[default]
+    Manual,
+    /// YouTube's automatic speech recognition.
+    Asr,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CaptionTrack {
+    pub video_id: String,
+    pub language_code: String,
+    /// Display name, e.g. "English (auto-generated)".
+    pub name: String,
+    pub kind: CaptionKind,
+    pub is_translatable: bool,
+    /// Signed timedtext URL. It expires, so fetch transcripts soon after listing tracks.
+    pub base_url: String,
+}
+
+/// Download format requested from the timedtext endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaptionFormat {
+    #[default]
+    Json3,
+    Srv3,
+}
+
+impl CaptionFormat {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            CaptionFormat::Json3 => "json3",
+            CaptionFormat::Srv3 => "srv3",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct TranscriptSegment {
+    pub start_seconds: f64,
+    pub duration_seconds: f64,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Transcript {
+    pub video_id: String,
+    pub language_code: String,
+    pub kind: CaptionKind,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    pub fn to_srt(&self) -> String {
+        self.segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| format!(
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                format_timestamp(segment.start_seconds, ','),
+                format_timestamp(segment.start_seconds + segment.duration_seconds, ','),
+                segment.text,
+            ))
+            .collect()
+    }
+
+    pub fn to_webvtt(&self) -> String {
+        let cues: String = self.segments
+            .iter()
+            .map(|segment| format!(
+                "{} --> {}\n{}\n\n",
+                format_timestamp(segment.start_seconds, '.'),
+                format_timestamp(segment.start_seconds + segment.duration_seconds, '.'),
+                segment.text,
+            ))
+            .collect();
+        format!("WEBVTT\n\n{cues}")
+    }
+
+    pub fn to_plain_text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// `HH:MM:SS,mmm` for SRT, `HH:MM:SS.mmm` for WebVTT.
+fn format_timestamp(seconds: f64, millis_separator: char) -> String {
+    let total_millis = (seconds * 1000.0).round() as u64;
+    let (hours, rest) = (total_millis / 3_600_000, total_millis % 3_600_000);
+    let (minutes, rest) = (rest / 60_000, rest % 60_000);
+    let (secs, millis) = (rest / 1000, rest % 1000);
+    format!("{hours:02}:{minutes:02}:{secs:02}{millis_separator}{millis:03}")
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common::read_fixture;
+use serde_json::Value;
+use yt_scraper::{
+    raw, BotCheckKind, CaptionFormat, CaptionKind, CaptionTrack, HttpResponse, RetryPolicy, Transport, YoutubeError,
+    YoutubeExtractor,
+};
+
+const SORRY_URL: &str = "https://www.google.com/sorry/index?continue=https://www.youtube.com/watch%3Fv%3DfxStandard1";
+
+/// Serves one page from `fixtures/bot_check` for every request, optionally after a redirect to `final_url`.
+struct BotCheckTransport {
+    page: &'static str,
+    status: u16,
+    final_url: Option<&'static str>,
+    requests: AtomicUsize,
+}
+
+impl BotCheckTransport {
+    fn new(page: &'static str, status: u16, final_url: Option<&'static str>) -> Self {
+        Self { page, status, final_url, requests: AtomicUsize::new(0) }
+    }
+
+    fn response(&self, url: &str) -> HttpResponse {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        HttpResponse {
+            status: self.status,
+            url: self.final_url.unwrap_or(url).to_string(),
+            headers: vec![
+                ("Content-Type".to_string(), "text/html; charset=UTF-8".to_string()),
+                ("Retry-After".to_string(), "0".to_string()),
+            ],
+            body: read_fixture("bot_check", self.page),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for BotCheckTransport {
+    async fn get_page(&self, url: &str, _headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        Ok(self.response(url))
+    }
+
+    async fn post_json(&self, url: &str, _headers: &[(String, String)], _body: &Value) -> Result<HttpResponse, YoutubeError> {
+        Ok(self.response(url))
+    }
+}
+
+async fn extract_through(transport: Arc<BotCheckTransport>) -> YoutubeError {
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(1)))
+        .transport(transport)
+        .build();
+    extractor.extract("fxStandard1").await.unwrap_err()
+}
+
+#[tokio::test]
+async fn captcha_page_is_reported_with_its_url() {
+    let transport = Arc::new(BotCheckTransport::new("captcha.html", 200, Some(SORRY_URL)));
+    let err = extract_through(transport.clone()).await;
+
+    match err {
+        YoutubeError::BotCheck { kind, url } => {
+            assert_eq!(kind, BotCheckKind::Captcha);
+            assert_eq!(url, SORRY_URL);
+        }
+        other => panic!("expected a bot check, got {other:?}"),
+    }
+    assert_eq!(transport.requests.load(Ordering::SeqCst), 1, "bot checks are not retried without a proxy pool");
+}
+
+#[tokio::test]
+async fn sign_in_wall_is_not_mistaken_for_an_unavailable_video() {
+    let err = extract_through(Arc::new(BotCheckTransport::new("sign_in.html", 200, None))).await;
+
+    assert!(matches!(
+        err,
+        YoutubeError::BotCheck { kind: BotCheckKind::SignInWall, ref url } if url.contains("watch?v=fxStandard1")
+    ), "{err:?}");
+}
+
+#[tokio::test]
+async fn sign_in_wall_is_recognised_in_other_languages() {
+    let err = extract_through(Arc::new(BotCheckTransport::new("sign_in_de.html", 200, None))).await;
+    assert!(matches!(err, YoutubeError::BotCheck { kind: BotCheckKind::SignInWall, .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn unusual_traffic_page_is_classified() {
+    let err = extract_through(Arc::new(BotCheckTransport::new("unusual_traffic.html", 200, Some(SORRY_URL)))).await;
+    assert!(matches!(err, YoutubeError::BotCheck { kind: BotCheckKind::UnusualTraffic, .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn pages_served_with_429_stay_rate_limited_and_are_retried() {
+    let transport = Arc::new(BotCheckTransport::new("too_many_requests.html", 429, None));
+    let err = extract_through(transport.clone()).await;
+
+    assert!(matches!(
+        err,
+        YoutubeError::RateLimited { retry_after: Some(_), bot_check: Some(BotCheckKind::RateLimitPage) }
+    ), "{err:?}");
+    assert_eq!(transport.requests.load(Ordering::SeqCst), RetryPolicy::default().max_attempts as usize);
+
+    let err = extract_through(Arc::new(BotCheckTransport::new("captcha.html", 429, Some(SORRY_URL)))).await;
+    assert!(matches!(err, YoutubeError::RateLimited { bot_check: Some(BotCheckKind::Captcha), .. }), "{err:?}");
+}
+
+/// Serves an srv3 transcript whose captions quote bot check wording, under the given Content-Type.
+struct TranscriptTransport {
+    content_type: &'static str,
+}
+
+#[async_trait]
+impl Transport for TranscriptTransport {
+    async fn get_page(&self, url: &str, _headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        let body = concat!(
+            r#"<?xml version="1.0" encoding="utf-8" ?><timedtext format="3"><body>"#,
+            r#"<p t="0" d="2400">we saw unusual traffic on the highway</p>"#,
+            r#"<p t="2400" d="3100">so please solve the captcha of rush hour</p>"#,
+            "</body></timedtext>",
+        );
+        Ok(HttpResponse {
+            status: 200,
+            url: url.to_string(),
+            headers: vec![("Content-Type".to_string(), self.content_type.to_string())],
+            body: body.to_string(),
+        })
+    }
+
+    async fn post_json(&self, _url: &str, _headers: &[(String, String)], _body: &Value) -> Result<HttpResponse, YoutubeError> {
+        Err(YoutubeError::Http { status: 404 })
+    }
+}
+
+#[tokio::test]
+async fn srv3_transcripts_quoting_bot_check_wording_are_not_bot_checks() {
+    let track = CaptionTrack {
+        video_id: "fxStandard1".to_string(),
+        language_code: "en".to_string(),
+        name: "English".to_string(),
+        kind: CaptionKind::Manual,
+        is_translatable: true,
+        base_url: "https://www.youtube.com/api/timedtext?v=fxStandard1&lang=en".to_string(),
+    };
+
+    // Caption downloads are never inspected, even when served with an HTML Content-Type.
+    for content_type in ["text/xml; charset=UTF-8", "text/html; charset=UTF-8"] {
+        let extractor = YoutubeExtractor::builder()
+            .request_delay(Duration::ZERO)
+            .transport(Arc::new(TranscriptTransport { content_type }))
+            .build();
+        let transcript = extractor.fetch_transcript(&track, CaptionFormat::Srv3).await.unwrap();
+        assert_eq!(transcript.segments[0].text, "we saw unusual traffic on the highway", "{content_type}");
+    }
+}
+
+#[test]
+fn ordinary_responses_are_not_bot_checks() {
+    let response = |status: u16, content_type: &str, body: String| HttpResponse {
+        status,
+        url: "https://www.youtube.com/watch?v=fxStandard1".to_string(),
+        headers: vec![("Content-Type".to_string(), content_type.to_string())],
+        body,
+    };
+
+    let watch_page = response(200, "text/html; charset=utf-8", read_fixture("standard", "watch.html"));
+    assert_eq!(raw::bot_check(&watch_page), None);
+    let age_gated = response(200, "text/html; charset=utf-8", read_fixture("age_gated", "watch.html"));
+    assert_eq!(raw::bot_check(&age_gated), None, "LOGIN_REQUIRED alone is not a bot check");
+    let bare_429 = response(429, "text/html; charset=utf-8", String::new());
+    assert_eq!(raw::bot_check(&bare_429), None);
+    let json = response(200, "application/json", r#"{"text":"unusual traffic"}"#.to_string());
+    assert_eq!(raw::bot_check(&json), None);
+    let xml = response(200, "text/xml", "<p>please solve the captcha of unusual traffic</p>".to_string());
+    assert_eq!(raw::bot_check(&xml), None, "markup alone does not make a page HTML");
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::fixture_extractor;
+use yt_scraper::{CaptionFormat, CaptionKind, Transcript, TranscriptSegment};
+
+#[tokio::test]
+async fn lists_manual_and_auto_generated_tracks() {
+    let tracks = fixture_extractor("standard").list_caption_tracks("fxStandard1").await.unwrap();
+
+    let summary: Vec<_> = tracks.iter().map(|t| (t.language_code.as_str(), t.kind)).collect();
+    assert_eq!(summary, [("en", CaptionKind::Asr), ("es", CaptionKind::Manual)]);
+    assert_eq!(tracks[0].name, "English (auto-generated)");
+    assert!(tracks.iter().all(|t| t.video_id == "fxStandard1" && t.is_translatable));
+}
+
+#[tokio::test]
+async fn json3_and_srv3_parse_to_the_same_segments() {
+    let extractor = fixture_extractor("standard");
+    let tracks = extractor.list_caption_tracks("fxStandard1").await.unwrap();
+
+    let json3 = extractor.fetch_transcript(&tracks[0], CaptionFormat::Json3).await.unwrap();
+    let srv3 = extractor.fetch_transcript(&tracks[0], CaptionFormat::Srv3).await.unwrap();
+
+    assert_eq!(json3, srv3);
+    assert_eq!(json3.kind, CaptionKind::Asr);
+    assert_eq!(json3.segments.len(), 3);
+    assert_eq!(json3.segments[2].text, "and it's in! what a goal");
+    assert_eq!(json3.segments[2].start_seconds, 65.0);
+}
+
+#[tokio::test]
+async fn srv3_entities_are_decoded() {
+    let extractor = fixture_extractor("standard");
+    let tracks = extractor.list_caption_tracks("fxStandard1").await.unwrap();
+
+    let transcript = extractor.fetch_transcript(&tracks[1], CaptionFormat::Srv3).await.unwrap();
+
+    assert_eq!(transcript.segments[1].text, "¡y es gol! qué golazo & más");
+}
+
+#[test]
+fn transcript_converts_to_srt_webvtt_and_text() {
+    let transcript = Transcript {
+        segments: vec![
+            TranscriptSegment { start_seconds: 0.0, duration_seconds: 2.4, text: "first".to_string() },
+            TranscriptSegment { start_seconds: 3723.5, duration_seconds: 1.25, text: "second".to_string() },
+        ],
+        ..Transcript::default()
+    };
+
+    assert_eq!(
+        transcript.to_srt(),
+        "1\n00:00:00,000 --> 00:00:02,400\nfirst\n\n2\n01:02:03,500 --> 01:02:04,750\nsecond\n\n",
+    );
+    assert_eq!(
+        transcript.to_webvtt(),
+        "WEBVTT\n\n00:00:00.000 --> 00:00:02.400\nfirst\n\n01:02:03.500 --> 01:02:04.750\nsecond\n\n",
+    );
+    assert_eq!(transcript.to_plain_text(), "first\nsecond");
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{fixture_clock, FixtureTransport, LocalTransport, StandInServer};
+use serde_json::json;
+use yt_scraper::{cassette, CookieJar, RecordingTransport, YoutubeError, YoutubeExtractor};
+
+#[tokio::test]
+async fn recorded_cassette_replays_offline() {
+    let dir = std::env::temp_dir().join(format!("yt_scraper_cassette_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let recorder = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .clock(fixture_clock())
+        .transport(Arc::new(RecordingTransport::new(Arc::new(FixtureTransport::new("standard")), &dir)))
+        .build();
+    let recorded = recorder.extract("fxStandard1").await.unwrap();
+
+    let replayer = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .clock(fixture_clock())
+        .replay_from(&dir)
+        .build();
+    let replayed = replayer.extract("fxStandard1").await.unwrap();
+    assert_eq!(recorded, replayed);
+
+    let err = replayer.extract("fxUnknown01").await.unwrap_err();
+    assert!(matches!(err, YoutubeError::CassetteMiss { .. }), "{err:?}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn cassettes_keep_session_cookies_out_but_pass_them_on() {
+    let dir = std::env::temp_dir().join(format!("yt_scraper_cassette_cookies_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let server = StandInServer::start("standard", &[
+        "YSC=fixtureSession; Domain=.youtube.com; Path=/; Secure; HttpOnly; SameSite=none",
+    ]).await;
+
+    let recorder = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .clock(fixture_clock())
+        .cookie_jar(Arc::new(CookieJar::new()))
+        .transport(Arc::new(RecordingTransport::new(Arc::new(LocalTransport::new(&server)), &dir)))
+        .build();
+    recorder.extract("fxStandard1").await.unwrap();
+
+    let innertube = server.requests().into_iter().find(|request| request.body.is_some()).unwrap();
+    assert!(innertube.header("Cookie").unwrap().contains("YSC=fixtureSession"));
+
+    for entry in std::fs::read_dir(&dir).unwrap() {
+        let recorded = std::fs::read_to_string(entry.unwrap().path()).unwrap().to_ascii_lowercase();
+        assert!(!recorded.contains("set-cookie"), "{recorded}");
+        assert!(!recorded.contains("fixturesession"), "{recorded}");
+        assert!(recorded.contains("content-type"), "{recorded}");
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cassette_keys_cover_method_and_url() {
+    let body = json!({"continuation": "token"});
+    assert_ne!(
+        cassette::post_key("https://www.youtube.com/youtubei/v1/next", &body),
+        cassette::post_key("https://www.youtube.com/youtubei/v1/browse", &body),
+    );
+    assert_ne!(cassette::get_key("https://www.youtube.com/watch?v=a"), cassette::get_key("https://www.youtube.com/watch?v=b"));
+}
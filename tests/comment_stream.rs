@@ -0,0 +1,333 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use async_trait::async_trait;
+use common::{fixture_clock, fixture_extractor, FixtureTransport};
+use futures::{StreamExt, TryStreamExt};
+use serde_json::Value;
+use yt_scraper::{Clock, Comment, CommentOptions, CrawlCheckpoint, CrawlEvent, HttpResponse, PendingItem, Transport, YoutubeError, YoutubeExtractor};
+
+/// Counts innertube calls so tests can tell how far a crawl went.
+struct CountingTransport {
+    inner: FixtureTransport,
+    posts: AtomicUsize,
+}
+
+#[async_trait]
+impl Transport for CountingTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        self.inner.get_page(url, headers).await
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        self.posts.fetch_add(1, Ordering::SeqCst);
+        self.inner.post_json(url, headers, body).await
+    }
+}
+
+/// Answers the first reply thread slowest and records how many requests overlap.
+struct SlowRepliesTransport {
+    inner: FixtureTransport,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+#[async_trait]
+impl Transport for SlowRepliesTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        self.inner.get_page(url, headers).await
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        if body["continuation"] == "std-replies-1" {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let response = self.inner.post_json(url, headers, body).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        response
+    }
+}
+
+/// Answers one continuation with `status` and `body` instead of its fixture.
+struct FailingTransport {
+    inner: FixtureTransport,
+    continuation: &'static str,
+    status: u16,
+    body: &'static str,
+}
+
+#[async_trait]
+impl Transport for FailingTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        self.inner.get_page(url, headers).await
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        if body["continuation"] != self.continuation {
+            return self.inner.post_json(url, headers, body).await;
+        }
+        Ok(HttpResponse { status: self.status, url: url.to_string(), headers: Vec::new(), body: self.body.to_string() })
+    }
+}
+
+fn failing_extractor(continuation: &'static str, status: u16, body: &'static str) -> YoutubeExtractor {
+    YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .clock(fixture_clock())
+        .transport(Arc::new(FailingTransport { inner: FixtureTransport::new("standard"), continuation, status, body }))
+        .build()
+}
+
+/// Crawls the standard fixture with `continuation` failing, then resumes from the last checkpoint
+/// without the failure and checks the two together give every comment exactly once.
+async fn resume_after_failure(continuation: &'static str, status: u16, body: &'static str) -> YoutubeError {
+    let failing = failing_extractor(continuation, status, body);
+    let mut events: Vec<_> = failing.crawl_comments("fxStandard1", CommentOptions::default()).collect().await;
+    let error = events.pop().unwrap().unwrap_err();
+
+    let mut seen = Vec::new();
+    let mut checkpoint = None;
+    for event in events {
+        match event.unwrap() {
+            CrawlEvent::Comment(comment) => seen.push(comment),
+            CrawlEvent::Checkpoint(saved) => checkpoint = Some((saved, seen.len())),
+        }
+    }
+    let (checkpoint, covered) = checkpoint.expect("a checkpoint before the failure");
+    seen.truncate(covered);
+
+    let extractor = fixture_extractor("standard");
+    let (_, expected) = extractor.extract("fxStandard1").await.unwrap();
+    let resumed: Vec<CrawlEvent> = extractor.resume_comments(checkpoint).try_collect().await.unwrap();
+    seen.extend(resumed.into_iter().filter_map(|event| match event {
+        CrawlEvent::Comment(comment) => Some(comment),
+        CrawlEvent::Checkpoint(_) => None,
+    }));
+    assert_eq!(seen, expected);
+    error
+}
+
+/// A clock that moves one second forward every time it is read.
+struct TickingClock {
+    ticks: AtomicUsize,
+}
+
+impl Clock for TickingClock {
+    fn now(&self) -> DateTime<Utc> {
+        let tick = self.ticks.fetch_add(1, Ordering::SeqCst);
+        "2025-08-25T12:00:00Z".parse::<DateTime<Utc>>().unwrap() + TimeDelta::seconds(tick as i64)
+    }
+}
+
+fn slow_replies_extractor(concurrency: usize) -> (YoutubeExtractor, Arc<SlowRepliesTransport>) {
+    let transport = Arc::new(SlowRepliesTransport {
+        inner: FixtureTransport::new("standard"),
+        in_flight: AtomicUsize::new(0),
+        max_in_flight: AtomicUsize::new(0),
+    });
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .reply_concurrency(concurrency)
+        .clock(fixture_clock())
+        .transport(transport.clone())
+        .build();
+    (extractor, transport)
+}
+
+#[tokio::test]
+async fn replies_are_fetched_concurrently_in_page_order() {
+    let (_, sequential) = slow_replies_extractor(1).0.extract("fxStandard1").await.unwrap();
+
+    let (extractor, transport) = slow_replies_extractor(4);
+    let (_, concurrent) = extractor.extract("fxStandard1").await.unwrap();
+    assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+    assert_eq!(concurrent, sequential);
+
+    let (extractor, transport) = slow_replies_extractor(4);
+    let streamed: Vec<_> = extractor.comments_stream("fxStandard1", CommentOptions::default())
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+    assert_eq!(streamed, sequential);
+}
+
+#[tokio::test]
+async fn stream_yields_the_same_comments_as_extract() {
+    let extractor = fixture_extractor("standard");
+    let (_, expected) = extractor.extract("fxStandard1").await.unwrap();
+
+    let streamed: Vec<_> = extractor.comments_stream("fxStandard1", CommentOptions::default())
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(streamed, expected);
+}
+
+#[tokio::test]
+async fn stopping_early_skips_later_pages() {
+    let transport = Arc::new(CountingTransport { inner: FixtureTransport::new("standard"), posts: AtomicUsize::new(0) });
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .reply_concurrency(1)
+        .transport(transport.clone())
+        .build();
+
+    let first_two: Vec<_> = extractor.comments_stream("fxStandard1", CommentOptions::default())
+        .take(2)
+        .collect()
+        .await;
+
+    assert_eq!(first_two.len(), 2);
+    // The first comments page and the replies of its first thread only.
+    assert_eq!(transport.posts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn max_comments_caps_the_stream() {
+    let comments: Vec<_> = fixture_extractor("standard")
+        .comments_stream("fxStandard1", CommentOptions::default().max_comments(4))
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(comments.len(), 4);
+}
+
+#[tokio::test]
+async fn disabled_comments_end_the_stream_with_an_error() {
+    let items: Vec<_> = fixture_extractor("comments_disabled")
+        .comments_stream("fxNoComment", CommentOptions::default())
+        .collect()
+        .await;
+
+    assert!(matches!(items.as_slice(), [Err(YoutubeError::CommentsDisabled)]));
+}
+
+#[tokio::test]
+async fn crawl_emits_a_checkpoint_per_page() {
+    let events: Vec<_> = fixture_extractor("standard")
+        .crawl_comments("fxStandard1", CommentOptions::default())
+        .try_collect()
+        .await
+        .unwrap();
+
+    let checkpoints: Vec<&CrawlCheckpoint> = events.iter()
+        .filter_map(|event| match event {
+            CrawlEvent::Checkpoint(checkpoint) => Some(checkpoint),
+            CrawlEvent::Comment(_) => None,
+        })
+        .collect();
+    assert_eq!(checkpoints.len(), 2);
+    assert_eq!(checkpoints[0].continuation.as_deref(), Some("std-comments-2"));
+    let pending_threads = checkpoints[0].pending.iter()
+        .filter(|item| matches!(item, PendingItem::Replies { .. }))
+        .count();
+    assert_eq!(pending_threads, 2);
+    assert_eq!(checkpoints[1].continuation, None);
+}
+
+#[tokio::test]
+async fn resuming_from_any_checkpoint_neither_skips_nor_repeats() {
+    let extractor = fixture_extractor("standard");
+    let events: Vec<_> = extractor.crawl_comments("fxStandard1", CommentOptions::default())
+        .try_collect()
+        .await
+        .unwrap();
+    let all_comments: Vec<Comment> = events.iter()
+        .filter_map(|event| match event {
+            CrawlEvent::Comment(comment) => Some(comment.clone()),
+            CrawlEvent::Checkpoint(_) => None,
+        })
+        .collect();
+
+    let mut seen = Vec::new();
+    for event in &events {
+        match event {
+            CrawlEvent::Comment(comment) => seen.push(comment.clone()),
+            CrawlEvent::Checkpoint(checkpoint) => {
+                // Round-trip through JSON as a crashed crawler would.
+                let saved = serde_json::to_string(checkpoint).unwrap();
+                let restored: CrawlCheckpoint = serde_json::from_str(&saved).unwrap();
+                assert_eq!(restored.emitted, seen.len());
+
+                let resumed: Vec<Comment> = extractor.resume_comments(restored)
+                    .try_filter_map(|event| async move {
+                        Ok(match event {
+                            CrawlEvent::Comment(comment) => Some(comment),
+                            CrawlEvent::Checkpoint(_) => None,
+                        })
+                    })
+                    .try_collect()
+                    .await
+                    .unwrap();
+
+                let combined: Vec<Comment> = seen.iter().cloned().chain(resumed).collect();
+                assert_eq!(combined, all_comments);
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn unparsable_pages_end_the_stream_and_are_retried_on_resume() {
+    let error = resume_after_failure("std-comments-2", 200, r#"{"responseContext":{}}"#).await;
+    assert!(matches!(error, YoutubeError::SchemaChanged { .. }), "{error:?}");
+}
+
+#[tokio::test]
+async fn failed_reply_threads_end_the_stream_and_are_retried_on_resume() {
+    let error = resume_after_failure("std-replies-3", 404, "").await;
+    assert!(matches!(error, YoutubeError::Http { status: 404 }), "{error:?}");
+
+    // The checkpoint right before the error keeps only the failed thread pending.
+    let mut events: Vec<_> = failing_extractor("std-replies-3", 404, "")
+        .crawl_comments("fxStandard1", CommentOptions::default())
+        .collect()
+        .await;
+    assert!(events.pop().unwrap().is_err());
+    let Some(Ok(CrawlEvent::Checkpoint(checkpoint))) = events.pop() else {
+        panic!("no checkpoint before the error");
+    };
+    let threads: Vec<_> = checkpoint.pending.iter()
+        .filter_map(|item| match item {
+            PendingItem::Replies { continuation, .. } => Some(continuation.as_str()),
+            PendingItem::Comment(_) => None,
+        })
+        .collect();
+    assert_eq!(threads, ["std-replies-3"]);
+}
+
+#[tokio::test]
+async fn extract_fails_instead_of_returning_a_truncated_crawl() {
+    // The second reply thread is fetched after the first thread's comments were gathered.
+    let error = failing_extractor("std-replies-3", 404, "").extract("fxStandard1").await.unwrap_err();
+    assert!(matches!(error, YoutubeError::Http { status: 404 }), "{error:?}");
+}
+
+#[tokio::test]
+async fn comments_of_one_response_share_its_fetch_time() {
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .clock(Arc::new(TickingClock { ticks: AtomicUsize::new(0) }))
+        .transport(Arc::new(FixtureTransport::new("standard")))
+        .build();
+    let comments: Vec<Comment> = extractor.comments_stream("fxStandard1", CommentOptions::default())
+        .try_collect()
+        .await
+        .unwrap();
+
+    // Two comments pages and two reply threads, each stamped once when it arrived.
+    let mut stamps: Vec<DateTime<Utc>> = comments.iter().map(|comment| comment.fetched_at).collect();
+    stamps.sort();
+    stamps.dedup();
+    assert_eq!(stamps.len(), 4, "{stamps:?}");
+}
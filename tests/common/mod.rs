@@ -0,0 +1,290 @@
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use chrono::{DateTime, Utc};
+use yt_scraper::{Comment, FixedClock, HttpResponse, ReqwestTransport, Transport, VideoInfo, YoutubeError, YoutubeExtractor};
+
+pub fn fixture_dir(case: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(case)
+}
+
+pub fn read_fixture(case: &str, file: &str) -> String {
+    std::fs::read_to_string(fixture_dir(case).join(file))
+        .unwrap_or_else(|e| panic!("missing fixture {case}/{file}: {e}"))
+}
+
+pub fn read_json_fixture(case: &str, file: &str) -> Value {
+    serde_json::from_str(&read_fixture(case, file)).expect("fixture is valid JSON")
+}
+
+/// Serves `watch.html` for every page fetch and `next/<continuation>.json` for every innertube call.
+pub struct FixtureTransport {
+    dir: PathBuf,
+}
+
+impl FixtureTransport {
+    pub fn new(case: &str) -> Self {
+        Self { dir: fixture_dir(case) }
+    }
+
+    /// Serves `timedtext/<lang>.<fmt>` for caption downloads.
+    fn timedtext(&self, url: &str) -> HttpResponse {
+        let url = reqwest::Url::parse(url).expect("timedtext URL is valid");
+        let param = |name: &str| url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .unwrap_or_default();
+        let path = self.dir.join("timedtext").join(format!("{}.{}", param("lang"), param("fmt")));
+        let (status, body) = match std::fs::read_to_string(&path) {
+            Ok(body) => (200, body),
+            Err(_) => (404, String::new()),
+        };
+        HttpResponse { status, url: url.to_string(), headers: Vec::new(), body }
+    }
+}
+
+#[async_trait]
+impl Transport for FixtureTransport {
+    async fn get_page(&self, url: &str, _headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        if url.contains("/api/timedtext") {
+            return Ok(self.timedtext(url));
+        }
+        let body = std::fs::read_to_string(self.dir.join("watch.html"))?;
+        Ok(HttpResponse {
+            status: 200,
+            url: url.to_string(),
+            headers: vec![("Content-Type".to_string(), "text/html; charset=utf-8".to_string())],
+            body,
+        })
+    }
+
+    async fn post_json(&self, url: &str, _headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        let token = body["continuation"].as_str().unwrap_or_default();
+        let path = self.dir.join("next").join(format!("{token}.json"));
+        let (status, body) = match std::fs::read_to_string(&path) {
+            Ok(body) => (200, body),
+            Err(_) => (404, "{}".to_string()),
+        };
+        Ok(HttpResponse {
+            status,
+            url: url.to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json; charset=UTF-8".to_string())],
+            body,
+        })
+    }
+}
+
+/// Two days after the standard fixture's upload, so snapshots don't depend on when tests run.
+pub const FIXTURE_NOW: &str = "2025-08-25T12:00:00Z";
+
+pub fn fixture_clock() -> Arc<FixedClock> {
+    let now: DateTime<Utc> = FIXTURE_NOW.parse().expect("valid fixture time");
+    Arc::new(FixedClock(now))
+}
+
+/// A request seen by [`CapturingTransport`]. `body` is `None` for page fetches.
+#[derive(Debug, Clone)]
+pub struct CapturedRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Value>,
+}
+
+impl CapturedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Serves fixtures like [`FixtureTransport`] and keeps every request for inspection.
+pub struct CapturingTransport {
+    inner: FixtureTransport,
+    pub requests: Mutex<Vec<CapturedRequest>>,
+}
+
+impl CapturingTransport {
+    pub fn new(case: &str) -> Self {
+        Self { inner: FixtureTransport::new(case), requests: Mutex::new(Vec::new()) }
+    }
+
+    pub fn requests(&self) -> Vec<CapturedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for CapturingTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        self.requests.lock().unwrap().push(CapturedRequest { url: url.to_string(), headers: headers.to_vec(), body: None });
+        self.inner.get_page(url, headers).await
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        self.requests.lock().unwrap().push(CapturedRequest { url: url.to_string(), headers: headers.to_vec(), body: Some(body.clone()) });
+        self.inner.post_json(url, headers, body).await
+    }
+}
+
+/// A plain HTTP server on localhost standing in for www.youtube.com.
+///
+/// Pages get `watch.html` and innertube POSTs get `next/<continuation>.json`, both sent with
+/// `set_cookies` as `Set-Cookie` headers. Every request is kept, with `url` holding the path.
+pub struct StandInServer {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<CapturedRequest>>>,
+}
+
+impl StandInServer {
+    pub async fn start(case: &str, set_cookies: &[&str]) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind localhost");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let dir = fixture_dir(case);
+        let set_cookies: Vec<String> = set_cookies.iter().map(|cookie| cookie.to_string()).collect();
+
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let (dir, set_cookies, seen) = (dir.clone(), set_cookies.clone(), seen.clone());
+                tokio::spawn(async move { Self::serve(stream, &dir, &set_cookies, &seen).await });
+            }
+        });
+
+        Self { base_url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<CapturedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    async fn serve(mut stream: TcpStream, dir: &Path, set_cookies: &[String], seen: &Mutex<Vec<CapturedRequest>>) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 8192];
+        let header_end = loop {
+            let read = stream.read(&mut chunk).await.unwrap_or(0);
+            if read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break end + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or_default().split(' ');
+        let (method, path) = (request_line.next().unwrap_or_default(), request_line.next().unwrap_or_default());
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+        let content_length = headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+            .unwrap_or(0);
+        while buffer.len() < header_end + content_length {
+            let read = stream.read(&mut chunk).await.unwrap_or(0);
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        let body: Option<Value> = (method == "POST")
+            .then(|| serde_json::from_slice(&buffer[header_end..]).ok())
+            .flatten();
+
+        let (status, content_type, payload) = match &body {
+            Some(body) => {
+                let token = body["continuation"].as_str().unwrap_or_default();
+                match std::fs::read_to_string(dir.join("next").join(format!("{token}.json"))) {
+                    Ok(json) => ("200 OK", "application/json; charset=UTF-8", json),
+                    Err(_) => ("404 Not Found", "application/json; charset=UTF-8", "{}".to_string()),
+                }
+            }
+            None => ("200 OK", "text/html; charset=utf-8", std::fs::read_to_string(dir.join("watch.html")).unwrap_or_default()),
+        };
+        seen.lock().unwrap().push(CapturedRequest { url: path.to_string(), headers, body });
+
+        let mut response = format!("HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n", payload.len());
+        for cookie in set_cookies {
+            response.push_str(&format!("Set-Cookie: {cookie}\r\n"));
+        }
+        response.push_str("\r\n");
+        response.push_str(&payload);
+        let _ = stream.write_all(response.as_bytes()).await;
+    }
+}
+
+/// The real reqwest transport, with www.youtube.com swapped for a [`StandInServer`].
+pub struct LocalTransport {
+    inner: ReqwestTransport,
+    base_url: String,
+}
+
+impl LocalTransport {
+    pub fn new(server: &StandInServer) -> Self {
+        Self { inner: ReqwestTransport::from_client(reqwest::Client::new()), base_url: server.base_url.clone() }
+    }
+
+    fn rewrite(&self, url: &str) -> String {
+        url.replacen("https://www.youtube.com", &self.base_url, 1)
+    }
+}
+
+#[async_trait]
+impl Transport for LocalTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        self.inner.get_page(&self.rewrite(url), headers).await
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        self.inner.post_json(&self.rewrite(url), headers, body).await
+    }
+}
+
+pub fn fixture_extractor(case: &str) -> YoutubeExtractor {
+    YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .clock(fixture_clock())
+        .transport(Arc::new(FixtureTransport::new(case)))
+        .build()
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Expected {
+    video_info: VideoInfo,
+    comments: Vec<Comment>,
+}
+
+/// Compares against `expected.json` in the fixture directory.
+///
+/// Run with `UPDATE_FIXTURES=1` to rewrite the expectation after an intentional change.
+pub fn assert_matches_expected(case: &str, video_info: &VideoInfo, comments: &[Comment]) {
+    let actual = Expected { video_info: video_info.clone(), comments: comments.to_vec() };
+    let path = fixture_dir(case).join("expected.json");
+
+    if std::env::var_os("UPDATE_FIXTURES").is_some() {
+        let json_str = serde_json::to_string_pretty(&actual).unwrap() + "\n";
+        std::fs::write(&path, json_str).unwrap();
+        return;
+    }
+
+    let expected: Expected = serde_json::from_str(&read_fixture(case, "expected.json"))
+        .expect("expected.json matches the current models, rerun with UPDATE_FIXTURES=1 after model changes");
+    assert_eq!(expected.video_info, actual.video_info, "video info mismatch for {case}");
+    assert_eq!(expected.comments.len(), actual.comments.len(), "comment count mismatch for {case}");
+    for (expected, actual) in expected.comments.iter().zip(&actual.comments) {
+        assert_eq!(expected, actual, "comment mismatch for {case}");
+    }
+}
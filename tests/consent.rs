@@ -0,0 +1,58 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common::FixtureTransport;
+use serde_json::Value;
+use yt_scraper::{HttpResponse, Transport, YoutubeError, YoutubeExtractor};
+
+/// Behaves like EU egress: the consent interstitial unless the request carries an accepted `SOCS` cookie.
+struct EuTransport {
+    consent: FixtureTransport,
+    watch: FixtureTransport,
+}
+
+#[async_trait]
+impl Transport for EuTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        let consented = headers.iter()
+            .any(|(name, value)| name == "Cookie" && value.split("; ").any(|cookie| cookie == "SOCS=CAI"));
+        if consented {
+            self.watch.get_page(url, headers).await
+        } else {
+            self.consent.get_page(url, headers).await
+        }
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        self.watch.post_json(url, headers, body).await
+    }
+}
+
+fn eu_extractor(bypass_consent: bool) -> YoutubeExtractor {
+    YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .bypass_consent(bypass_consent)
+        .transport(Arc::new(EuTransport { consent: FixtureTransport::new("consent"), watch: FixtureTransport::new("standard") }))
+        .build()
+}
+
+#[tokio::test]
+async fn consent_cookies_get_past_the_interstitial() {
+    let (video_info, comments) = eu_extractor(true).extract("fxStandard1").await.unwrap();
+
+    assert_eq!(video_info.title, "Fixture City v. Test United | HIGHLIGHTS | 8/23/2025");
+    assert!(!comments.is_empty());
+}
+
+#[tokio::test]
+async fn consent_page_is_reported_when_not_bypassed() {
+    let err = eu_extractor(false).extract("fxStandard1").await.unwrap_err();
+
+    assert!(matches!(err, YoutubeError::ConsentRequired), "{err:?}");
+
+    let err = eu_extractor(false).list_caption_tracks("fxStandard1").await.unwrap_err();
+    assert!(matches!(err, YoutubeError::ConsentRequired), "{err:?}");
+}
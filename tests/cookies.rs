@@ -0,0 +1,114 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{fixture_clock, LocalTransport, StandInServer};
+use yt_scraper::{Cookie, CookieJar, YoutubeExtractor};
+
+const COOKIES_TXT: &str = "# Netscape HTTP Cookie File
+# This is a generated file! Do not edit.
+
+.youtube.com\tTRUE\t/\tTRUE\t1790000000\tSAPISID\tfixtureSapisid
+#HttpOnly_.youtube.com\tTRUE\t/\tTRUE\t1790000000\tLOGIN_INFO\tfixtureLogin
+.youtube.com\tTRUE\t/\tFALSE\t0\tPREF\thl=en&gl=US&f6=40000000
+accounts.google.com\tFALSE\t/\tTRUE\t1790000000\tLSID\tnotForYoutube
+not a cookie line
+";
+
+#[test]
+fn netscape_cookies_txt_is_parsed_and_written_back() {
+    let jar = CookieJar::parse_netscape(COOKIES_TXT);
+
+    let cookies = jar.cookies();
+    assert_eq!(cookies.len(), 4);
+    assert_eq!(jar.get("LOGIN_INFO").as_deref(), Some("fixtureLogin"));
+    assert_eq!(cookies[0].domain, "youtube.com");
+    assert!(cookies[0].include_subdomains && cookies[0].secure);
+    assert_eq!(cookies[0].expires.map(|expires| expires.timestamp()), Some(1_790_000_000));
+    assert_eq!(cookies[2].expires, None, "an expiry of 0 is a session cookie");
+    assert!(!cookies[3].include_subdomains);
+
+    assert_eq!(CookieJar::parse_netscape(&jar.to_netscape()).cookies(), cookies);
+}
+
+#[tokio::test]
+async fn session_cookies_and_sapisidhash_reach_innertube() {
+    let server = StandInServer::start("standard", &[
+        "YSC=fixtureSession; Domain=.youtube.com; Path=/; Secure; HttpOnly; SameSite=none",
+        "VISITOR_INFO1_LIVE=fixtureVisitor; Domain=.youtube.com; Path=/; Max-Age=15552000; Secure",
+        "GPS=1; Domain=.youtube.com; Path=/; Expires=Mon, 25-Aug-2025 11:00:00 GMT; Secure",
+    ]).await;
+    let jar = Arc::new(CookieJar::parse_netscape(COOKIES_TXT));
+
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .clock(fixture_clock())
+        .cookie_jar(jar.clone())
+        .transport(Arc::new(LocalTransport::new(&server)))
+        .build();
+    let (_, comments) = extractor.extract("fxStandard1").await.unwrap();
+    assert!(!comments.is_empty());
+
+    let requests = server.requests();
+    let watch = &requests[0];
+    assert!(watch.url.starts_with("/watch?v=fxStandard1"));
+    let watch_cookies = watch.header("Cookie").unwrap();
+    assert!(watch_cookies.contains("SAPISID=fixtureSapisid"));
+    assert!(watch_cookies.contains("PREF=hl=en&gl=US&f6=40000000"), "the jar overrides the configured PREF: {watch_cookies}");
+    assert!(!watch_cookies.contains("LSID"));
+    assert_eq!(watch.header("Authorization"), None);
+
+    let innertube: Vec<_> = requests.iter().filter(|request| request.body.is_some()).collect();
+    assert!(!innertube.is_empty());
+    for request in innertube {
+        let cookies = request.header("Cookie").unwrap();
+        assert!(cookies.contains("YSC=fixtureSession"), "{cookies}");
+        assert!(cookies.contains("VISITOR_INFO1_LIVE=fixtureVisitor"), "{cookies}");
+        assert!(!cookies.contains("GPS="), "expired cookies are dropped: {cookies}");
+        assert_eq!(
+            request.header("Authorization"),
+            Some("SAPISIDHASH 1756123200_774103c17d5114fd475f4025cd56d591ea970fd9"),
+        );
+        assert_eq!(request.header("X-Origin"), Some("https://www.youtube.com"));
+    }
+
+    // The caller's jar now holds the session too, ready to be saved.
+    assert_eq!(jar.get("YSC").as_deref(), Some("fixtureSession"));
+    assert!(extractor.cookie_jar().to_netscape().contains("VISITOR_INFO1_LIVE\tfixtureVisitor"));
+}
+
+#[test]
+fn inserting_replaces_the_same_cookie() {
+    let jar = CookieJar::new();
+    jar.insert(Cookie::new(".youtube.com", "SAPISID", "old"));
+    jar.insert(Cookie::new("youtube.com", "SAPISID", "new"));
+
+    assert_eq!(jar.cookies().len(), 1);
+    assert_eq!(jar.get("SAPISID").as_deref(), Some("new"));
+}
+
+#[tokio::test]
+async fn foreign_domains_and_endless_max_ages_are_handled() {
+    let server = StandInServer::start("standard", &[
+        "FOREVER=1; Domain=.youtube.com; Path=/; Max-Age=99999999999999; Secure",
+        "FOREIGN=1; Domain=.example.com; Path=/; Secure",
+        "TLD=1; Domain=com; Path=/; Secure",
+        "LOOKALIKE=1; Domain=ube.com; Path=/; Secure",
+    ]).await;
+
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .clock(fixture_clock())
+        .transport(Arc::new(LocalTransport::new(&server)))
+        .build();
+    extractor.extract("fxStandard1").await.unwrap();
+
+    let cookies = extractor.cookie_jar().cookies();
+    let forever = cookies.iter().find(|cookie| cookie.name == "FOREVER").unwrap();
+    assert_eq!(forever.domain, "youtube.com");
+    assert_eq!(forever.expires, None);
+    for name in ["FOREIGN", "TLD", "LOOKALIKE"] {
+        assert!(cookies.iter().all(|cookie| cookie.name != name), "{name} was stored: {cookies:?}");
+    }
+}
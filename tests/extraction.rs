@@ -0,0 +1,270 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use common::{assert_matches_expected, fixture_extractor, CapturingTransport, FixtureTransport};
+use yt_scraper::{CommentSort, DescriptionLinkKind, YoutubeError, YoutubeExtractor};
+
+#[tokio::test]
+async fn standard_video() {
+    let (video_info, comments) = fixture_extractor("standard").extract("fxStandard1").await.unwrap();
+
+    assert_eq!(video_info.title, "Fixture City v. Test United | HIGHLIGHTS | 8/23/2025");
+    assert_eq!(video_info.views, 799_831);
+    assert_eq!(video_info.like_count, 10_464);
+    assert_eq!(video_info.comment_count, 821);
+    assert_eq!(video_info.duration_seconds, 212);
+    assert_eq!(video_info.category, "Sports");
+    assert_eq!(video_info.keywords, ["football", "highlights"]);
+    assert_eq!(video_info.publish_date_iso.as_deref(), Some("2025-08-23T09:12:45-07:00"));
+    assert_eq!(video_info.thumbnails.last().map(|t| (t.width, t.height)), Some((1280, 720)));
+    assert_eq!(video_info.playability.status, "OK");
+    assert_eq!(comments.len(), 9);
+    assert_matches_expected("standard", &video_info, &comments);
+}
+
+#[tokio::test]
+async fn comments_disabled_returns_video_info_without_comments() {
+    let (video_info, comments) = fixture_extractor("comments_disabled").extract("fxNoComment").await.unwrap();
+
+    assert!(comments.is_empty());
+    assert_matches_expected("comments_disabled", &video_info, &comments);
+}
+
+#[tokio::test]
+async fn members_only_video_is_unavailable() {
+    let err = fixture_extractor("members_only").extract("fxMembers01").await.unwrap_err();
+
+    match err {
+        YoutubeError::VideoUnavailable { reason } => assert!(reason.contains("members-only"), "{reason}"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn age_gated_video_is_age_restricted() {
+    let err = fixture_extractor("age_gated").extract("fxAgeGate01").await.unwrap_err();
+
+    assert!(matches!(err, YoutubeError::AgeRestricted { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn age_gate_is_recognised_in_other_languages() {
+    let err = fixture_extractor("age_gated_de").extract("fxAgeGate01").await.unwrap_err();
+
+    assert!(matches!(err, YoutubeError::AgeRestricted { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn private_videos_are_recognised_in_other_languages() {
+    let err = fixture_extractor("private_de").extract("fxPrivate01").await.unwrap_err();
+
+    assert!(matches!(err, YoutubeError::Private), "{err:?}");
+}
+
+#[tokio::test]
+async fn sign_in_reasons_merely_containing_age_are_not_age_gates() {
+    let err = fixture_extractor("login_required").extract("fxAgeGate01").await.unwrap_err();
+
+    assert!(matches!(err, YoutubeError::VideoUnavailable { ref reason } if reason == "Sign in to view this page"), "{err:?}");
+}
+
+#[tokio::test]
+async fn live_stream() {
+    let (video_info, comments) = fixture_extractor("live_stream").extract("fxLiveNow01").await.unwrap();
+
+    // The watch page only shows concurrent viewers, the player response has the total.
+    assert_eq!(video_info.views, 98_120_455);
+    assert!(video_info.is_live_content);
+    assert_eq!(comments.len(), 2);
+    assert_matches_expected("live_stream", &video_info, &comments);
+}
+
+#[tokio::test]
+async fn short() {
+    let (video_info, comments) = fixture_extractor("short").extract("fxShort0001").await.unwrap();
+
+    assert_eq!(video_info.views, 2_104_567);
+    assert_eq!(video_info.duration_seconds, 31);
+    assert_eq!(video_info.comment_count, 1_500);
+    assert_matches_expected("short", &video_info, &comments);
+}
+
+#[tokio::test]
+async fn pinned_comment() {
+    let (video_info, comments) = fixture_extractor("pinned_comment").extract("fxPinned001").await.unwrap();
+
+    assert_eq!(comments[0].display_name, "@FixtureBuilds");
+    assert_matches_expected("pinned_comment", &video_info, &comments);
+}
+
+#[tokio::test]
+async fn comment_flags() {
+    let (_, comments) = fixture_extractor("pinned_comment").extract("fxPinned001").await.unwrap();
+
+    let flags: Vec<_> = comments.iter().map(|c| (c.is_pinned, c.is_creator, c.is_hearted)).collect();
+    assert_eq!(flags, [(true, true, false), (false, false, true), (false, false, false)]);
+    assert_eq!(comments[0].author_handle.as_deref(), Some("@FixtureBuilds"));
+    assert_eq!(comments[1].member_badge.as_ref().map(|b| b.label.as_str()), Some("Member (6 months)"));
+    assert!(comments[2].member_badge.is_none());
+
+    let (_, comments) = fixture_extractor("standard").extract("fxStandard1").await.unwrap();
+    let edited: Vec<_> = comments.iter().filter(|c| c.is_edited).map(|c| c.content.as_str()).collect();
+    assert_eq!(edited, ["1:05 that first goal 🔥"]);
+}
+
+#[tokio::test]
+async fn abbreviated_comment_counts_are_expanded() {
+    let (_, comments) = fixture_extractor("standard").extract("fxStandard1").await.unwrap();
+
+    let first = comments.iter().find(|c| c.content == "What a finish in the 88th minute!").unwrap();
+    assert_eq!((first.like_count, first.like_count_exact), (1_200, false));
+    assert_eq!((first.reply_count, first.reply_count_exact), (3, true));
+
+    let keeper = comments.iter().find(|c| c.content == "Keeper deserves man of the match").unwrap();
+    assert_eq!((keeper.like_count, keeper.like_count_exact), (311, true));
+}
+
+#[tokio::test]
+async fn chapters_come_from_the_chapters_panel() {
+    let (video_info, _) = fixture_extractor("pinned_comment").extract("fxPinned001").await.unwrap();
+
+    let chapters: Vec<_> = video_info.chapters.iter().map(|c| (c.start_seconds, c.end_seconds)).collect();
+    assert_eq!(chapters, [(0, 95), (95, 610), (610, 1104), (1104, 1312)]);
+    assert_eq!(video_info.chapters[1].title, "Framing the door");
+    assert!(video_info.chapters.iter().all(|c| c.thumbnail.is_some()));
+}
+
+#[tokio::test]
+async fn chapters_fall_back_to_description_timestamps() {
+    let (video_info, _) = fixture_extractor("standard").extract("fxStandard1").await.unwrap();
+
+    let chapters: Vec<_> = video_info.chapters.iter()
+        .map(|c| (c.title.as_str(), c.start_seconds, c.end_seconds))
+        .collect();
+    assert_eq!(chapters, [("Kick off", 0, 65), ("First goal", 65, 150), ("Red card", 150, 212)]);
+}
+
+#[tokio::test]
+async fn description_links_are_typed() {
+    let (video_info, _) = fixture_extractor("standard").extract("fxStandard1").await.unwrap();
+    let links_of = |kind| video_info.description_links.iter()
+        .filter(|l| l.kind == kind)
+        .map(|l| (l.text.as_str(), l.url.as_str()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(links_of(DescriptionLinkKind::Hashtag), [
+        ("#Football", "https://www.youtube.com/hashtag/football"),
+        ("#Highlights", "https://www.youtube.com/hashtag/highlights"),
+    ]);
+    assert_eq!(links_of(DescriptionLinkKind::Mention), [("@FixtureRadio", "https://www.youtube.com/@FixtureRadio")]);
+    // External links are unwrapped from youtube.com/redirect.
+    assert!(links_of(DescriptionLinkKind::Url).contains(&("https://tickets.example.com/derby", "https://tickets.example.com/derby")));
+    let timestamps: Vec<_> = video_info.description_links.iter().filter_map(|l| l.start_seconds).collect();
+    assert_eq!(timestamps, [0, 65, 150]);
+}
+
+#[tokio::test]
+async fn long_reply_thread() {
+    let (video_info, comments) = fixture_extractor("long_replies").extract("fxReplies01").await.unwrap();
+
+    let reply_orders: Vec<i32> = comments.iter().filter(|c| c.comment_level == 1).map(|c| c.reply_order).collect();
+    assert_eq!(reply_orders, (1..=14).collect::<Vec<_>>());
+    assert_matches_expected("long_replies", &video_info, &comments);
+}
+
+#[tokio::test]
+async fn reply_pages_are_capped() {
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .max_reply_pages(1)
+        .transport(Arc::new(FixtureTransport::new("long_replies")))
+        .build();
+
+    let (_, comments) = extractor.extract("fxReplies01").await.unwrap();
+
+    assert_eq!(comments.iter().filter(|c| c.comment_level == 1).count(), 10);
+}
+
+#[tokio::test]
+async fn newest_first_follows_the_sort_menu() {
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .comment_sort(CommentSort::Newest)
+        .transport(Arc::new(FixtureTransport::new("standard")))
+        .build();
+
+    let (_, comments) = extractor.extract("fxStandard1").await.unwrap();
+
+    let top_level: Vec<&str> = comments.iter()
+        .filter(|c| c.comment_level == 0)
+        .map(|c| c.published_time.as_str())
+        .collect();
+    assert_eq!(top_level, ["5 hours ago", "21 hours ago", "1 day ago (edited)", "2 days ago", "2 days ago"]);
+    assert_eq!(comments.len(), 9);
+}
+
+/// The continuations sent when the watch page has no comment panel and the first comments page no sort menu.
+async fn synthetic_continuations(sort: CommentSort) -> Vec<String> {
+    let transport = Arc::new(CapturingTransport::new("no_comment_panel"));
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .transport(transport.clone())
+        .build();
+
+    let options = extractor.config().comment_options().sort(sort);
+    let _ = extractor.comments_stream("fxNoPanel01", options).collect::<Vec<_>>().await;
+
+    transport.requests().iter()
+        .filter_map(|request| request.body.as_ref())
+        .map(|body| body["continuation"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn synthetic_continuation_tokens_encode_the_sort_order() {
+    // Protobuf field 6 after the inner video ID holds the sort menu index, 0 for top and 1 for
+    // newest, the same values youtube-comment-downloader uses as SORT_BY_POPULAR/SORT_BY_RECENT.
+    const TOP: &str = "Eg0SC2Z4Tm9QYW5lbDAxGAYyJyIRIgtmeE5vUGFuZWwwMTAAeAIwAEIQY29tbWVudHMtc2VjdGlvbg==";
+    const NEWEST: &str = "Eg0SC2Z4Tm9QYW5lbDAxGAYyJyIRIgtmeE5vUGFuZWwwMTABeAIwAEIQY29tbWVudHMtc2VjdGlvbg==";
+
+    assert_eq!(synthetic_continuations(CommentSort::Top).await, [TOP]);
+    // Newest asks for the top page first to look for its sort menu, then falls back.
+    assert_eq!(synthetic_continuations(CommentSort::Newest).await, [TOP, NEWEST]);
+}
+
+#[tokio::test]
+async fn language_and_region_are_sent_everywhere() {
+    let transport = Arc::new(CapturingTransport::new("standard"));
+    let extractor = YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .language("de")
+        .region("AT")
+        .transport(transport.clone())
+        .build();
+
+    extractor.extract("fxStandard1").await.unwrap();
+
+    let requests = transport.requests();
+    let watch = &requests[0];
+    assert!(watch.url.contains("hl=de&gl=AT"), "{}", watch.url);
+    for request in &requests {
+        assert_eq!(request.header("Accept-Language"), Some("de-AT,de;q=0.9"));
+        assert!(request.header("Cookie").unwrap().starts_with("PREF=hl=de&gl=AT"));
+    }
+    let innertube: Vec<_> = requests.iter().filter_map(|r| r.body.as_ref()).collect();
+    assert!(!innertube.is_empty());
+    for body in innertube {
+        assert_eq!(body["context"]["client"]["hl"], "de");
+        assert_eq!(body["context"]["client"]["gl"], "AT");
+    }
+}
+
+#[tokio::test]
+async fn invalid_input_is_rejected() {
+    let err = fixture_extractor("standard").extract("https://example.com/").await.unwrap_err();
+
+    assert!(matches!(err, YoutubeError::InvalidVideoId(_)), "{err:?}");
+}
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use yt_scraper::{raw, EstimatedTime, TimePrecision, YoutubeExtractor};
+
+const NOW: &str = "2025-08-25T12:00:00Z";
+
+struct Case {
+    extractor: YoutubeExtractor,
+    now: DateTime<Utc>,
+}
+
+fn locale(language: &str) -> Case {
+    Case {
+        extractor: YoutubeExtractor::builder().language(language).build(),
+        now: NOW.parse().unwrap(),
+    }
+}
+
+impl Case {
+    fn count(&self, text: &str) -> Option<u64> {
+        raw::parse_count(&self.extractor, text)
+    }
+
+    fn ago(&self, text: &str) -> Option<(i64, TimePrecision)> {
+        raw::parse_published_time(&self.extractor, text, self.now)
+            .map(|t| ((self.now - t.timestamp).num_hours(), t.precision))
+    }
+
+    fn date(&self, text: &str) -> Option<String> {
+        raw::parse_upload_date(&self.extractor, text, self.now)
+            .map(|EstimatedTime { timestamp, .. }| timestamp.format("%Y-%m-%d").to_string())
+    }
+}
+
+#[test]
+fn english() {
+    let en = locale("en-US");
+
+    assert_eq!(en.count("799,831 views"), Some(799_831));
+    assert_eq!(en.count("like this video along with 10,464 other people"), Some(10_464));
+    assert_eq!(en.count("1.2K"), Some(1_200));
+    assert_eq!(en.count("12,34,567 views"), Some(1_234_567));
+    assert_eq!(en.count("1.5 lakh views"), Some(150_000));
+    assert_eq!(en.count("No views"), None);
+    assert_eq!(en.ago("an hour ago"), Some((1, TimePrecision::Hours)));
+    assert_eq!(en.date("Streamed live on Aug 23, 2025").as_deref(), Some("2025-08-23"));
+    assert_eq!(en.date("8/23/2025").as_deref(), Some("2025-08-23"));
+}
+
+#[test]
+fn german() {
+    let de = locale("de-DE");
+
+    assert_eq!(de.count("799.831 Aufrufe"), Some(799_831));
+    assert_eq!(de.count("1,2 Mio. Aufrufe"), Some(1_200_000));
+    assert_eq!(de.count("3,4 Tsd."), Some(3_400));
+    assert_eq!(de.count("12 Kommentare"), Some(12));
+    assert_eq!(de.ago("vor 2 Tagen"), Some((48, TimePrecision::Days)));
+    assert_eq!(de.ago("vor einem Monat"), Some((31 * 24, TimePrecision::Months)));
+    assert_eq!(de.date("Premiere am 23.08.2025").as_deref(), Some("2025-08-23"));
+    assert_eq!(de.date("1. März 2025").as_deref(), Some("2025-03-01"));
+}
+
+#[test]
+fn spanish() {
+    let es = locale("es");
+
+    assert_eq!(es.count("1,5 mil visualizaciones"), Some(1_500));
+    assert_eq!(es.count("2,3 M de visualizaciones"), Some(2_300_000));
+    assert_eq!(es.count("1 me gusta"), Some(1));
+    assert_eq!(es.ago("hace 3 horas"), Some((3, TimePrecision::Hours)));
+    assert_eq!(es.ago("hace 1 semana"), Some((7 * 24, TimePrecision::Weeks)));
+    assert_eq!(es.date("23 ago 2025").as_deref(), Some("2025-08-23"));
+    // Untranslated responses still parse.
+    assert_eq!(es.ago("2 days ago"), Some((48, TimePrecision::Days)));
+}
+
+#[test]
+fn portuguese() {
+    let pt = locale("pt-BR");
+
+    assert_eq!(pt.count("1,2 mil visualizações"), Some(1_200));
+    assert_eq!(pt.count("3,4 mi de visualizações"), Some(3_400_000));
+    assert_eq!(pt.ago("há 5 dias"), Some((5 * 24, TimePrecision::Days)));
+    assert_eq!(pt.date("Transmitido ao vivo em 23 de ago. de 2025").as_deref(), Some("2025-08-23"));
+}
+
+#[test]
+fn french() {
+    let fr = locale("fr");
+
+    assert_eq!(fr.count("1 234 567 vues"), Some(1_234_567));
+    assert_eq!(fr.count("2,5 k"), Some(2_500));
+    assert_eq!(fr.count("1,1 Md de vues"), Some(1_100_000_000));
+    assert_eq!(fr.ago("il y a 2 ans"), Some((731 * 24, TimePrecision::Years)));
+    assert_eq!(fr.date("23 août 2025").as_deref(), Some("2025-08-23"));
+}
+
+#[test]
+fn russian() {
+    let ru = locale("ru");
+
+    assert_eq!(ru.count("3,4 тыс. просмотров"), Some(3_400));
+    assert_eq!(ru.count("1,2 млн просмотров"), Some(1_200_000));
+    assert_eq!(ru.ago("5 дней назад"), Some((5 * 24, TimePrecision::Days)));
+    assert_eq!(ru.ago("3 часа назад"), Some((3, TimePrecision::Hours)));
+    assert_eq!(ru.date("23 авг. 2025 г.").as_deref(), Some("2025-08-23"));
+}
+
+#[test]
+fn japanese() {
+    let ja = locale("ja");
+
+    assert_eq!(ja.count("79万 回視聴"), Some(790_000));
+    assert_eq!(ja.count("1.2億回視聴"), Some(120_000_000));
+    assert_eq!(ja.ago("3 時間前"), Some((3, TimePrecision::Hours)));
+    assert_eq!(ja.ago("1 か月前"), Some((31 * 24, TimePrecision::Months)));
+    assert_eq!(ja.date("2025/08/23").as_deref(), Some("2025-08-23"));
+}
+
+#[test]
+fn chinese() {
+    let zh = locale("zh-CN");
+
+    assert_eq!(zh.count("12.3万次观看"), Some(123_000));
+    assert_eq!(zh.ago("2天前"), Some((48, TimePrecision::Days)));
+    assert_eq!(zh.date("2025年8月23日").as_deref(), Some("2025-08-23"));
+}
+
+#[test]
+fn hindi() {
+    let hi = locale("hi");
+
+    assert_eq!(hi.count("1.2 लाख बार देखा गया"), Some(120_000));
+    assert_eq!(hi.ago("2 दिन पहले"), Some((48, TimePrecision::Days)));
+}
+
+#[test]
+fn absurd_relative_times_are_dropped() {
+    let en = locale("en");
+
+    assert_eq!(en.ago("100000000 days ago"), None);
+    assert_eq!(en.ago("4000000000 seconds ago").map(|(_, precision)| precision), Some(TimePrecision::Seconds));
+    assert_eq!(en.ago("4000000000 weeks ago"), None);
+    assert_eq!(en.date("Streamed live 100000000 days ago"), None);
+}
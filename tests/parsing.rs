@@ -0,0 +1,155 @@
+mod common;
+
+use chrono::{DateTime, Utc};
+use common::{read_fixture, read_json_fixture, FIXTURE_NOW};
+use serde_json::json;
+use yt_scraper::{raw, EstimatedTime, TimePrecision, YoutubeError, YoutubeExtractor};
+
+#[test]
+fn find_json_end_skips_braces_inside_strings() {
+    let text = r#"{"a": {"b": "}{"}, "c": "say \"}\""};var next = {};"#;
+
+    let end = raw::find_json_end(text).unwrap();
+    assert_eq!(&text[..end], r#"{"a": {"b": "}{"}, "c": "say \"}\""}"#);
+    assert_eq!(raw::find_json_end(r#"{"unterminated": {"#), None);
+}
+
+#[test]
+fn parse_count_handles_separators_and_suffixes() {
+    let extractor = YoutubeExtractor::new();
+
+    assert_eq!(raw::parse_count(&extractor, "799,831"), Some(799_831));
+    assert_eq!(raw::parse_count(&extractor, "10K"), Some(10_000));
+    assert_eq!(raw::parse_count(&extractor, "1.5k"), Some(1_500));
+    assert_eq!(raw::parse_count(&extractor, "2.1M"), Some(2_100_000));
+    assert_eq!(raw::parse_count(&extractor, "2.3K"), Some(2_300));
+    assert_eq!(raw::parse_count(&extractor, "3B"), Some(3_000_000_000));
+    assert_eq!(raw::parse_count(&extractor, "views"), None);
+}
+
+fn at(text: &str, precision: TimePrecision) -> Option<EstimatedTime> {
+    Some(EstimatedTime { timestamp: text.parse().unwrap(), precision })
+}
+
+#[test]
+fn relative_times_resolve_against_the_fetch_instant() {
+    let extractor = YoutubeExtractor::new();
+    let now: DateTime<Utc> = "2025-03-31T12:00:00Z".parse().unwrap();
+    let parse = |text| raw::parse_published_time(&extractor, text, now);
+
+    assert_eq!(parse("45 seconds ago"), at("2025-03-31T11:59:15Z", TimePrecision::Seconds));
+    assert_eq!(parse("21 hours ago"), at("2025-03-30T15:00:00Z", TimePrecision::Hours));
+    assert_eq!(parse("1 day ago (edited)"), at("2025-03-30T12:00:00Z", TimePrecision::Days));
+    assert_eq!(parse("a week ago"), at("2025-03-24T12:00:00Z", TimePrecision::Weeks));
+    assert_eq!(parse("1 month ago"), at("2025-02-28T12:00:00Z", TimePrecision::Months));
+    assert_eq!(parse("2 years ago"), at("2023-03-31T12:00:00Z", TimePrecision::Years));
+    assert_eq!(parse("MISSING_PUBLISHED_TIME"), None);
+}
+
+#[test]
+fn upload_dates_accept_calendar_and_relative_forms() {
+    let extractor = YoutubeExtractor::new();
+    let now: DateTime<Utc> = "2025-08-25T12:00:00Z".parse().unwrap();
+    let parse = |text| raw::parse_upload_date(&extractor, text, now);
+
+    assert_eq!(parse("Aug 23, 2025"), at("2025-08-23T00:00:00Z", TimePrecision::Days));
+    assert_eq!(parse("Premiered Sep 1, 2025"), at("2025-09-01T00:00:00Z", TimePrecision::Days));
+    assert_eq!(parse("Streamed live on Feb 1, 2023"), at("2023-02-01T00:00:00Z", TimePrecision::Days));
+    assert_eq!(parse("Streamed live 3 hours ago"), at("2025-08-25T09:00:00Z", TimePrecision::Hours));
+    assert_eq!(parse("Feb 30, 2025"), None);
+    assert_eq!(parse(""), None);
+}
+
+#[test]
+fn extract_initial_data_from_watch_page() {
+    let webpage = read_fixture("standard", "watch.html");
+
+    let data = raw::extract_initial_data(&webpage).unwrap();
+    assert!(data["contents"]["twoColumnWatchNextResults"].is_object());
+
+    let err = raw::extract_initial_data("<html></html>").unwrap_err();
+    assert!(matches!(err, YoutubeError::InitialDataMissing));
+}
+
+#[test]
+fn oversized_description_offsets_and_chapter_times_are_skipped() {
+    let description = json!({
+        "content": "#derby https://example.com",
+        "commandRuns": [
+            {"startIndex": 0, "length": 6, "onTap": {"innertubeCommand": {"commandMetadata": {"webCommandMetadata": {"url": "/hashtag/derby"}}}}},
+            {"startIndex": 7, "length": u64::MAX, "onTap": {"innertubeCommand": {"urlEndpoint": {"url": "https://example.com"}}}},
+        ],
+    });
+    let links = raw::get_description_links(&description, "fxStandard1");
+    let texts: Vec<_> = links.iter().map(|link| link.text.as_str()).collect();
+    assert_eq!(texts, ["#derby"]);
+
+    let marker = |time: &str| json!({"macroMarkersListItemRenderer": {
+        "title": {"simpleText": time},
+        "timeDescription": {"simpleText": time},
+    }});
+    let initial_data = json!({"engagementPanels": [{"engagementPanelSectionListRenderer": {
+        "panelIdentifier": "engagement-panel-macro-markers-description-chapters",
+        "content": {"macroMarkersListRenderer": {"contents": [
+            marker("0:00"),
+            marker("99999999999999999:00:00"),
+            marker("1:02:03"),
+        ]}},
+    }}]});
+    let chapters = raw::get_panel_chapters(&YoutubeExtractor::new(), &initial_data);
+    let starts: Vec<_> = chapters.iter().map(|chapter| chapter.start_seconds).collect();
+    assert_eq!(starts, [0, 3723]);
+}
+
+#[tokio::test]
+async fn next_continuation_token_for_first_and_later_pages() {
+    let extractor = YoutubeExtractor::new();
+    let first_page = read_json_fixture("standard", "next/std-comments-1.json");
+    let last_page = read_json_fixture("standard", "next/std-comments-2.json");
+
+    let token = raw::get_next_continuation_token(&extractor, &first_page, 1).await;
+    assert_eq!(token.as_deref(), Some("std-comments-2"));
+    assert_eq!(raw::get_next_continuation_token(&extractor, &last_page, 2).await, None);
+}
+
+#[tokio::test]
+async fn comment_info_from_entity_mutation() {
+    let extractor = YoutubeExtractor::new();
+    let page = read_json_fixture("standard", "next/std-comments-1.json");
+    let mutations = page["frameworkUpdates"]["entityBatchUpdate"]["mutations"].as_array().unwrap();
+    let fetched_at: DateTime<Utc> = FIXTURE_NOW.parse().unwrap();
+
+    let comment = raw::get_comment_info(&extractor, &mutations[2], "fxStandard1", fetched_at).await.unwrap();
+    assert_eq!(comment.comment_id, "UgxStd002fixture4AaABAg");
+    assert_eq!(comment.video_id, "fxStandard1");
+    assert_eq!(comment.display_name, "@StdViewer2");
+    assert!(comment.user_verified);
+    assert_eq!(comment.content, "Keeper deserves man of the match");
+    assert_eq!(comment.published_time, "2 days ago");
+    assert_eq!(comment.like_count, 311);
+    assert_eq!(comment.reply_count, 0);
+    assert_eq!(comment.fetched_at, fetched_at);
+
+    // Toolbar state mutations carry no comment.
+    assert!(raw::get_comment_info(&extractor, &mutations[1], "fxStandard1", fetched_at).await.is_none());
+}
+
+#[tokio::test]
+async fn edited_marker_follows_the_interface_language() {
+    let page = read_json_fixture("german_comments", "next/de-comments-1.json");
+    let mutations = page["frameworkUpdates"]["entityBatchUpdate"]["mutations"].as_array().unwrap();
+
+    let german = YoutubeExtractor::builder().language("de").build();
+    let fetched_at: DateTime<Utc> = FIXTURE_NOW.parse().unwrap();
+    let edited = raw::get_comment_info(&german, &mutations[4], "fxStandard1", fetched_at).await.unwrap();
+    assert_eq!(edited.published_time, "vor 1 Tag (bearbeitet)");
+    assert!(edited.is_edited);
+    assert!(edited.published_at.is_some());
+    let unedited = raw::get_comment_info(&german, &mutations[2], "fxStandard1", fetched_at).await.unwrap();
+    assert!(!unedited.is_edited);
+
+    // English markers are still recognised, for responses that ignored `hl`.
+    let page = read_json_fixture("standard", "next/std-comments-1.json");
+    let mutations = page["frameworkUpdates"]["entityBatchUpdate"]["mutations"].as_array().unwrap();
+    assert!(raw::get_comment_info(&german, &mutations[4], "fxStandard1", fetched_at).await.unwrap().is_edited);
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use common::{fixture_extractor, FixtureTransport};
+use futures::future;
+use serde_json::Value;
+use yt_scraper::{HttpResponse, RateLimiter, RetryPolicy, Transport, YoutubeError, YoutubeExtractor};
+
+#[tokio::test]
+async fn concurrent_callers_are_spaced_out() {
+    let limiter = Arc::new(RateLimiter::new(Duration::from_millis(20)));
+    let started = Instant::now();
+
+    future::join_all((0..4).map(|_| limiter.acquire())).await;
+
+    // The first slot is immediate, the other three wait 20 ms each.
+    assert!(started.elapsed() >= Duration::from_millis(60), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn zero_interval_never_waits() {
+    let limiter = RateLimiter::new(Duration::ZERO);
+    let started = Instant::now();
+
+    for _ in 0..100 {
+        limiter.acquire().await;
+    }
+
+    assert!(started.elapsed() < Duration::from_millis(20));
+}
+
+#[tokio::test]
+async fn burst_goes_out_at_once_then_refills() {
+    let limiter = RateLimiter::with_burst(Duration::from_millis(50), 3);
+    let started = Instant::now();
+
+    future::join_all((0..3).map(|_| limiter.acquire())).await;
+    assert!(started.elapsed() < Duration::from_millis(40), "{:?}", started.elapsed());
+
+    limiter.acquire().await;
+    assert!(started.elapsed() >= Duration::from_millis(50), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn pause_holds_back_every_caller() {
+    let limiter = RateLimiter::new(Duration::ZERO);
+    limiter.pause_for(Duration::from_millis(40)).await;
+    let started = Instant::now();
+
+    future::join_all((0..3).map(|_| limiter.acquire())).await;
+
+    assert!(started.elapsed() >= Duration::from_millis(35), "{:?}", started.elapsed());
+}
+
+#[test]
+fn backoff_doubles_up_to_the_cap_and_honours_retry_after() {
+    let policy = RetryPolicy::default()
+        .initial_backoff(Duration::from_millis(500))
+        .max_backoff(Duration::from_secs(3))
+        .jitter(false);
+    let server_error = YoutubeError::Http { status: 503 };
+
+    assert_eq!(policy.backoff(1, &server_error), Duration::from_millis(500));
+    assert_eq!(policy.backoff(3, &server_error), Duration::from_secs(2));
+    assert_eq!(policy.backoff(10, &server_error), Duration::from_secs(3));
+
+    let throttled = YoutubeError::RateLimited { retry_after: Some(Duration::from_secs(2)), bot_check: None };
+    assert_eq!(policy.backoff(1, &throttled), Duration::from_secs(2));
+    let throttled = YoutubeError::RateLimited { retry_after: Some(Duration::from_secs(60)), bot_check: None };
+    assert_eq!(policy.backoff(1, &throttled), Duration::from_secs(3));
+
+    let jittered = policy.jitter(true);
+    for _ in 0..20 {
+        let backoff = jittered.backoff(2, &server_error);
+        assert!((Duration::from_millis(500)..=Duration::from_secs(1)).contains(&backoff), "{backoff:?}");
+    }
+
+    assert!(!policy.is_retryable(&YoutubeError::Http { status: 404 }));
+    assert!(!policy.is_retryable(&YoutubeError::ConsentRequired));
+}
+
+/// Answers the first `failures` requests of each kind with `status`, then serves the standard fixture.
+struct FlakyTransport {
+    inner: FixtureTransport,
+    status: u16,
+    failures: usize,
+    pages: AtomicUsize,
+    posts: AtomicUsize,
+}
+
+impl FlakyTransport {
+    fn new(status: u16, failures: usize) -> Self {
+        Self { inner: FixtureTransport::new("standard"), status, failures, pages: AtomicUsize::new(0), posts: AtomicUsize::new(0) }
+    }
+
+    fn failure(&self, url: &str) -> HttpResponse {
+        HttpResponse {
+            status: self.status,
+            url: url.to_string(),
+            headers: vec![("Retry-After".to_string(), "0".to_string())],
+            body: String::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for FlakyTransport {
+    async fn get_page(&self, url: &str, headers: &[(String, String)]) -> Result<HttpResponse, YoutubeError> {
+        if self.pages.fetch_add(1, Ordering::SeqCst) < self.failures {
+            return Ok(self.failure(url));
+        }
+        self.inner.get_page(url, headers).await
+    }
+
+    async fn post_json(&self, url: &str, headers: &[(String, String)], body: &Value) -> Result<HttpResponse, YoutubeError> {
+        if self.posts.fetch_add(1, Ordering::SeqCst) < self.failures {
+            return Ok(self.failure(url));
+        }
+        self.inner.post_json(url, headers, body).await
+    }
+}
+
+fn flaky_extractor(transport: Arc<FlakyTransport>, attempts: u32) -> YoutubeExtractor {
+    YoutubeExtractor::builder()
+        .request_delay(Duration::ZERO)
+        .retry_policy(RetryPolicy::default().max_attempts(attempts).initial_backoff(Duration::from_millis(1)))
+        .transport(transport)
+        .build()
+}
+
+#[tokio::test]
+async fn throttling_and_server_errors_are_retried() {
+    for status in [429, 503] {
+        let transport = Arc::new(FlakyTransport::new(status, 2));
+        let (_, comments) = flaky_extractor(transport.clone(), 3).extract("fxStandard1").await.unwrap();
+
+        let (_, expected) = fixture_extractor("standard").extract("fxStandard1").await.unwrap();
+        assert_eq!(comments.len(), expected.len(), "HTTP {status}");
+        assert_eq!(transport.pages.load(Ordering::SeqCst), 3, "HTTP {status}");
+    }
+}
+
+#[tokio::test]
+async fn retrying_stops_after_max_attempts() {
+    let transport = Arc::new(FlakyTransport::new(503, usize::MAX));
+    let err = flaky_extractor(transport.clone(), 3).extract("fxStandard1").await.unwrap_err();
+
+    assert!(matches!(err, YoutubeError::Http { status: 503 }), "{err:?}");
+    assert_eq!(transport.pages.load(Ordering::SeqCst), 3);
+
+    let transport = Arc::new(FlakyTransport::new(404, usize::MAX));
+    let err = flaky_extractor(transport.clone(), 3).extract("fxStandard1").await.unwrap_err();
+
+    assert!(matches!(err, YoutubeError::Http { status: 404 }), "{err:?}");
+    assert_eq!(transport.pages.load(Ordering::SeqCst), 1, "client errors are not retried");
+}
@@ -0,0 +1,82 @@
+use yt_scraper::{ChannelRef, VideoId, YoutubeError, YoutubeUrl};
+
+fn video_id(input: &str) -> String {
+    input.parse::<VideoId>().unwrap_or_else(|e| panic!("{input}: {e}")).to_string()
+}
+
+#[test]
+fn video_id_from_every_video_url_shape() {
+    let cases = [
+        "dQw4w9WgXcQ",
+        "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+        "https://www.youtube.com/watch?feature=share&v=dQw4w9WgXcQ&t=42s",
+        "www.youtube.com/watch?v=dQw4w9WgXcQ",
+        "https://youtu.be/dQw4w9WgXcQ",
+        "https://youtu.be/dQw4w9WgXcQ?si=abcdef&t=10",
+        "https://www.youtube.com/shorts/dQw4w9WgXcQ",
+        "https://www.youtube.com/live/dQw4w9WgXcQ?feature=share",
+        "https://www.youtube.com/embed/dQw4w9WgXcQ?start=30",
+        "https://m.youtube.com/watch?v=dQw4w9WgXcQ",
+        "https://music.youtube.com/watch?v=dQw4w9WgXcQ&list=RDAMVMdQw4w9WgXcQ",
+        "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ",
+        "https://www.youtube.com/attribution_link?a=xyz&u=/watch%3Fv%3DdQw4w9WgXcQ%26feature%3Dshare",
+        "https://www.youtube.com/watch?v=dQw4w9WgXcQ#t=1m30s",
+        "  https://youtu.be/dQw4w9WgXcQ  ",
+    ];
+    for input in cases {
+        assert_eq!(video_id(input), "dQw4w9WgXcQ", "{input}");
+    }
+}
+
+#[test]
+fn video_id_rejects_bad_alphabet_and_length() {
+    for input in ["dQw4w9WgXc", "dQw4w9WgXcQQ", "dQw4w9WgX!Q", "https://youtu.be/short", "https://example.com/watch?v=dQw4w9WgXcQ", ""] {
+        let err = input.parse::<VideoId>().unwrap_err();
+        assert!(matches!(err, YoutubeError::InvalidVideoId(_)), "{input}: {err:?}");
+    }
+}
+
+#[test]
+fn video_url_carries_start_time_and_playlist() {
+    let url: YoutubeUrl = "https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PLfixture123&t=1h2m3s".parse().unwrap();
+    assert_eq!(url, YoutubeUrl::Video {
+        id: "dQw4w9WgXcQ".parse().unwrap(),
+        start_seconds: Some(3723),
+        playlist_id: Some("PLfixture123".to_string()),
+    });
+
+    let url: YoutubeUrl = "https://youtu.be/dQw4w9WgXcQ#t=90".parse().unwrap();
+    assert!(matches!(url, YoutubeUrl::Video { start_seconds: Some(90), .. }));
+}
+
+#[test]
+fn overflowing_start_times_are_dropped() {
+    for input in ["https://youtu.be/dQw4w9WgXcQ?t=99999999999999999h", "https://youtu.be/dQw4w9WgXcQ?t=18446744073709551615s1s"] {
+        let url: YoutubeUrl = input.parse().unwrap();
+        assert!(matches!(url, YoutubeUrl::Video { start_seconds: None, .. }), "{input}: {url:?}");
+    }
+}
+
+#[test]
+fn channel_playlist_and_search_urls() {
+    let cases = [
+        ("https://www.youtube.com/@NBCSports", YoutubeUrl::Channel(ChannelRef::Handle("NBCSports".to_string()))),
+        ("https://www.youtube.com/@NBCSports/videos", YoutubeUrl::Channel(ChannelRef::Handle("NBCSports".to_string()))),
+        ("https://www.youtube.com/channel/UCqZQlzSHbVJrwrn5XvzrzcA", YoutubeUrl::Channel(ChannelRef::Id("UCqZQlzSHbVJrwrn5XvzrzcA".to_string()))),
+        ("https://www.youtube.com/c/nbcsports", YoutubeUrl::Channel(ChannelRef::Custom("nbcsports".to_string()))),
+        ("https://www.youtube.com/user/nbcsports", YoutubeUrl::Channel(ChannelRef::User("nbcsports".to_string()))),
+        ("https://www.youtube.com/playlist?list=PLfixture123", YoutubeUrl::Playlist { id: "PLfixture123".to_string() }),
+        ("https://www.youtube.com/results?search_query=premier+league", YoutubeUrl::Search { query: "premier league".to_string() }),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(input.parse::<YoutubeUrl>().unwrap(), expected, "{input}");
+    }
+}
+
+#[test]
+fn unrecognized_urls_are_errors() {
+    for input in ["https://example.com/@someone", "https://www.youtube.com/feed/trending", "not a url at all"] {
+        let err = input.parse::<YoutubeUrl>().unwrap_err();
+        assert!(matches!(err, YoutubeError::UnrecognizedUrl(_)), "{input}: {err:?}");
+    }
+}